@@ -2,7 +2,9 @@ use std::collections::HashMap;
 
 use intuitils::config::IntuiConfig;
 use ratatui::style::Color;
-use serde::Deserialize;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+use transmission_rpc::types::{IdleMode, Priority, RatioMode, TorrentSetArgs};
 
 #[derive(Deserialize)]
 pub struct CategoriesConfig {
@@ -51,17 +53,139 @@ pub struct Category {
     #[serde(default = "default_color_category")]
     pub color: Color,
     pub default_dir: Option<String>,
+    /// Download limit in kB/s.
+    pub download_limit: Option<usize>,
+    /// Upload limit in kB/s.
+    pub upload_limit: Option<usize>,
+    pub seed_ratio: Option<f64>,
+    /// Stop seeding after this many minutes of inactivity.
+    pub seed_idle_limit: Option<u16>,
+    pub bandwidth_priority: Option<BandwidthPriority>,
+    #[serde(default)]
+    pub start_paused: bool,
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    pub tracker_regex: Option<Regex>,
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    pub name_regex: Option<Regex>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum BandwidthPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl From<BandwidthPriority> for Priority {
+    fn from(priority: BandwidthPriority) -> Self {
+        match priority {
+            BandwidthPriority::Low => Priority::Low,
+            BandwidthPriority::Normal => Priority::Normal,
+            BandwidthPriority::High => Priority::High,
+        }
+    }
 }
 
 fn default_color_category() -> Color {
     Color::White
 }
 
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let regex = String::deserialize(deserializer)?;
+    Regex::new(&regex).map(Some).map_err(de::Error::custom)
+}
+
+impl Category {
+    /// Torrent settings this category imposes, None if it doesn't have any.
+    pub fn torrent_set_args(&self) -> Option<TorrentSetArgs> {
+        let mut args = TorrentSetArgs::default();
+        let mut has_policy = false;
+
+        if let Some(limit) = self.download_limit {
+            args = args.download_limit(limit).download_limited(true);
+            has_policy = true;
+        }
+        if let Some(limit) = self.upload_limit {
+            args = args.upload_limit(limit).upload_limited(true);
+            has_policy = true;
+        }
+        if let Some(ratio) = self.seed_ratio {
+            args = args
+                .seed_ratio_limit(ratio)
+                .seed_ratio_mode(RatioMode::Single);
+            has_policy = true;
+        }
+        if let Some(minutes) = self.seed_idle_limit {
+            args = args
+                .seed_idle_limit(minutes)
+                .seed_idle_mode(IdleMode::Single);
+            has_policy = true;
+        }
+        if let Some(priority) = self.bandwidth_priority {
+            args = args.bandwidth_priority(priority.into());
+            has_policy = true;
+        }
+
+        has_policy.then_some(args)
+    }
+
+    /// Whether a torrent with this name and trackers should get this category.
+    /// Every regex that is set has to match, and at least one has to be set.
+    pub fn matches(&self, name: &str, trackers: &[String]) -> bool {
+        if self.tracker_regex.is_none() && self.name_regex.is_none() {
+            return false;
+        }
+
+        self.name_regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(name))
+            && self
+                .tracker_regex
+                .as_ref()
+                .is_none_or(|regex| trackers.iter().any(|tracker| regex.is_match(tracker)))
+    }
+}
+
 impl CategoriesConfig {
     pub fn is_empty(&self) -> bool {
         self.categories.is_empty()
     }
 
+    pub fn has_auto_assignment(&self) -> bool {
+        self.categories
+            .iter()
+            .any(|category| category.tracker_regex.is_some() || category.name_regex.is_some())
+    }
+
+    /// First category (in config order) that should be assigned automatically
+    /// to a torrent with this name and trackers.
+    pub fn auto_assign(&self, name: &str, trackers: &[String]) -> Option<&Category> {
+        self.categories
+            .iter()
+            .find(|category| category.matches(name, trackers))
+    }
+
+    /// Returns the label acting as a category: the first label that is
+    /// a configured category. Other labels are left to other tools.
+    pub fn category_label<'a>(&self, labels: &'a [String]) -> Option<&'a String> {
+        labels.iter().find(|label| self.map.contains_key(*label))
+    }
+
+    /// Puts `category` in front of the labels in place of the current
+    /// category, or just removes the current one if `category` is empty.
+    pub fn set_category(&self, labels: &mut Vec<String>, category: &str) {
+        if let Some(current) = self.category_label(labels).cloned() {
+            labels.retain(|label| *label != current);
+        }
+        labels.retain(|label| label != category);
+        if !category.is_empty() {
+            labels.insert(0, category.to_string());
+        }
+    }
+
     fn populate_hashmap(&mut self) {
         for category in &self.categories {
             self.map.insert(category.name.clone(), category.clone());
@@ -89,3 +213,56 @@ impl CategoriesConfig {
         self.max_icon_len = max_icon_len;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories() -> CategoriesConfig {
+        let mut config: CategoriesConfig =
+            toml::from_str("[[categories]]\nname = \"Movies\"\n[[categories]]\nname = \"Music\"")
+                .unwrap();
+        config.post_init();
+        config
+    }
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn unconfigured_labels_are_not_categories() {
+        let categories = categories();
+
+        assert_eq!(categories.category_label(&labels(&["foreign"])), None);
+        assert_eq!(
+            categories.category_label(&labels(&["foreign", "Music"])),
+            Some(&"Music".to_string())
+        );
+    }
+
+    #[test]
+    fn changing_category_keeps_unconfigured_labels() {
+        let categories = categories();
+
+        let mut torrent_labels = labels(&["foreign"]);
+        categories.set_category(&mut torrent_labels, "Movies");
+        assert_eq!(torrent_labels, labels(&["Movies", "foreign"]));
+
+        categories.set_category(&mut torrent_labels, "Music");
+        assert_eq!(torrent_labels, labels(&["Music", "foreign"]));
+    }
+
+    #[test]
+    fn clearing_category_keeps_unconfigured_labels() {
+        let categories = categories();
+
+        let mut torrent_labels = labels(&["foreign", "Movies", "other"]);
+        categories.set_category(&mut torrent_labels, "");
+        assert_eq!(torrent_labels, labels(&["foreign", "other"]));
+
+        let mut torrent_labels = labels(&["foreign"]);
+        categories.set_category(&mut torrent_labels, "");
+        assert_eq!(torrent_labels, labels(&["foreign"]));
+    }
+}
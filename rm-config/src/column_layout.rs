@@ -0,0 +1,163 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use intuitils::config::IntuiConfig;
+use rm_shared::header::Header;
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+
+use crate::{main_config::MainConfig, CONFIG};
+
+const STATE_FILENAME: &str = "columns.toml";
+
+/// Columns of the torrents table and their widths, as set up in config.toml
+/// or rearranged in the column editor.
+///
+/// A layout saved from the editor to `rustmission/columns.toml` in the XDG
+/// state dir takes precedence over the config, unless config.toml has been
+/// edited since.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ColumnLayout {
+    pub headers: Vec<Header>,
+    /// Widths of `headers`, in the same order. 0 keeps a header's default width.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub header_widths: Vec<u16>,
+}
+
+impl ColumnLayout {
+    pub fn load() -> Self {
+        match Self::load_state() {
+            Ok(Some(layout)) => return layout,
+            Ok(None) => (),
+            Err(e) => tracing::error!("Failed to load the column layout: {e:?}"),
+        }
+
+        Self {
+            headers: CONFIG.torrents_tab.headers.clone(),
+            header_widths: CONFIG.torrents_tab.header_widths.clone(),
+        }
+    }
+
+    fn load_state() -> Result<Option<Self>> {
+        let Some(path) = state_path() else {
+            return Ok(None);
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        if is_older_than_config(&path) {
+            tracing::info!("Ignoring {path:?}, config.toml has been edited since it was saved");
+            return Ok(None);
+        }
+
+        let layout: Self =
+            toml::from_str(&contents).wrap_err_with(|| format!("Invalid layout at {path:?}"))?;
+
+        let custom_headers_len = CONFIG.torrents_tab.custom_headers.len();
+        for header in &layout.headers {
+            if let Header::Custom(idx) = header {
+                if *idx >= custom_headers_len {
+                    return Err(eyre!(
+                        "{path:?}: Custom header {idx} is used, but only {custom_headers_len} custom_headers are defined"
+                    ));
+                }
+            }
+        }
+
+        Ok(Some(layout))
+    }
+
+    /// Width the user set for the header at `idx`, if any.
+    pub fn width(&self, idx: usize) -> Option<u16> {
+        self.header_widths
+            .get(idx)
+            .copied()
+            .filter(|width| *width != 0)
+    }
+
+    /// Saves the layout to the UI state file, leaving config.toml as it is.
+    pub fn save_to_state(&self) -> Result<PathBuf> {
+        let path = state_path().ok_or_else(|| eyre!("XDG state directory is unavailable"))?;
+        fs::write(&path, toml::to_string(&self.trimmed())?)?;
+        Ok(path)
+    }
+
+    /// Writes the layout into the `[torrents_tab]` section of config.toml,
+    /// keeping the rest of the file, comments included, intact.
+    ///
+    /// The UI state file is removed, as it would override the config.
+    pub fn save_to_config(&self) -> Result<PathBuf> {
+        let path = MainConfig::path().clone();
+        let mut config: DocumentMut = fs::read_to_string(&path)?.parse()?;
+        let layout: DocumentMut = toml::to_string(&self.trimmed())?.parse()?;
+
+        let torrents_tab = config
+            .entry("torrents_tab")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| eyre!("torrents_tab is not a table"))?;
+        for key in ["headers", "header_widths"] {
+            match (torrents_tab.get_mut(key), layout.get(key)) {
+                // Assigning in place keeps the comments above the key.
+                (Some(item), Some(value)) => *item = value.clone(),
+                (None, Some(value)) => {
+                    torrents_tab.insert(key, value.clone());
+                }
+                (Some(_), None) => {
+                    torrents_tab.remove(key);
+                }
+                (None, None) => (),
+            }
+        }
+
+        fs::write(&path, config.to_string())?;
+
+        if let Some(state_path) = state_path() {
+            match fs::remove_file(state_path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => (),
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Drops widths past the last header and the list altogether if no
+    /// header has its width set.
+    fn trimmed(&self) -> Self {
+        let mut header_widths = self.header_widths.clone();
+        header_widths.truncate(self.headers.len());
+        if header_widths.iter().all(|width| *width == 0) {
+            header_widths.clear();
+        }
+
+        Self {
+            headers: self.headers.clone(),
+            header_widths,
+        }
+    }
+}
+
+fn is_older_than_config(state_path: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified());
+
+    match (modified(state_path), modified(MainConfig::path())) {
+        (Ok(state), Ok(config)) => state < config,
+        _ => false,
+    }
+}
+
+fn state_path() -> Option<PathBuf> {
+    let xdg = xdg::BaseDirectories::with_prefix("rustmission").ok()?;
+    xdg.place_state_file(STATE_FILENAME).ok()
+}
@@ -0,0 +1,56 @@
+use intuitils::user_action::UserAction;
+use serde::{Deserialize, Serialize};
+
+/// Editing actions available in every text prompt.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InputAction {
+    GoToPrevChar,
+    GoToNextChar,
+    GoToPrevWord,
+    GoToNextWord,
+    GoToStart,
+    GoToEnd,
+    DeletePrevChar,
+    DeleteNextChar,
+    DeletePrevWord,
+    DeleteNextWord,
+    DeleteLine,
+    DeleteTillEnd,
+    HistoryPrev,
+    HistoryNext,
+    NextCompletion,
+    PrevCompletion,
+    SelectAllMatching,
+}
+
+impl UserAction for InputAction {
+    fn desc(&self) -> &'static str {
+        match self {
+            InputAction::GoToPrevChar => "move cursor left",
+            InputAction::GoToNextChar => "move cursor right, accept completion",
+            InputAction::GoToPrevWord => "move cursor a word left",
+            InputAction::GoToNextWord => "move cursor a word right",
+            InputAction::GoToStart => "move cursor to start",
+            InputAction::GoToEnd => "move cursor to end",
+            InputAction::DeletePrevChar => "delete previous character",
+            InputAction::DeleteNextChar => "delete next character",
+            InputAction::DeletePrevWord => "delete previous word",
+            InputAction::DeleteNextWord => "delete next word",
+            InputAction::DeleteLine => "delete whole line",
+            InputAction::DeleteTillEnd => "delete till end of line",
+            InputAction::HistoryPrev => "previous history entry",
+            InputAction::HistoryNext => "next history entry",
+            InputAction::NextCompletion => "next completion",
+            InputAction::PrevCompletion => "previous completion",
+            InputAction::SelectAllMatching => "select all torrents matching the filter",
+        }
+    }
+
+    fn merge_desc_with(&self, other: &InputAction) -> Option<&'static str> {
+        match (&self, other) {
+            (Self::HistoryPrev, Self::HistoryNext) => Some("previous / next history entry"),
+            (Self::NextCompletion, Self::PrevCompletion) => Some("next / previous completion"),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod general;
+pub mod input;
 pub mod search_tab;
 pub mod torrents_tab;
 pub mod torrents_tab_file_viewer;
@@ -5,26 +5,56 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TorrentsAction {
     AddMagnet,
+    AddTorrentFile,
     MoveTorrent,
     Rename,
     Pause,
+    PauseAll,
+    ResumeAll,
     Delete,
     ShowFiles,
     ShowStats,
     ChangeCategory,
+    AddLabel,
+    RemoveLabel,
+    VisualSelect,
+    SelectAll,
+    InvertSelection,
+    EditColumns,
+    CycleGrouping,
+    ToggleGroups,
 }
 
 impl UserAction for TorrentsAction {
     fn desc(&self) -> &'static str {
         match self {
             TorrentsAction::AddMagnet => "add a magnet",
+            TorrentsAction::AddTorrentFile => "add .torrent files",
             TorrentsAction::MoveTorrent => "move torrent download directory",
             TorrentsAction::Pause => "pause/unpause",
+            TorrentsAction::PauseAll => "pause all torrents",
+            TorrentsAction::ResumeAll => "resume all torrents",
             TorrentsAction::Delete => "delete",
             TorrentsAction::ShowFiles => "show files",
             TorrentsAction::ShowStats => "show statistics",
             TorrentsAction::ChangeCategory => "change category",
+            TorrentsAction::AddLabel => "add a label",
+            TorrentsAction::RemoveLabel => "remove a label",
+            TorrentsAction::VisualSelect => "select a range (visual mode)",
+            TorrentsAction::SelectAll => "select all listed torrents",
+            TorrentsAction::InvertSelection => "invert selection",
             TorrentsAction::Rename => "rename torrent path",
+            TorrentsAction::EditColumns => "show, reorder and resize columns",
+            TorrentsAction::CycleGrouping => "group by category / directory / tracker / status",
+            TorrentsAction::ToggleGroups => "collapse / expand all groups",
+        }
+    }
+
+    fn merge_desc_with(&self, other: &TorrentsAction) -> Option<&'static str> {
+        match (&self, other) {
+            (Self::PauseAll, Self::ResumeAll) => Some("pause / resume all torrents"),
+            (Self::ResumeAll, Self::PauseAll) => Some("resume / pause all torrents"),
+            _ => None,
         }
     }
 }
@@ -33,13 +63,24 @@ impl From<TorrentsAction> for Action {
     fn from(value: TorrentsAction) -> Self {
         match value {
             TorrentsAction::AddMagnet => Action::AddMagnet,
+            TorrentsAction::AddTorrentFile => Action::AddTorrentFile,
             TorrentsAction::MoveTorrent => Action::MoveTorrent,
             TorrentsAction::Pause => Action::Pause,
+            TorrentsAction::PauseAll => Action::PauseAll,
+            TorrentsAction::ResumeAll => Action::ResumeAll,
             TorrentsAction::Delete => Action::Delete,
             TorrentsAction::ShowFiles => Action::ShowFiles,
             TorrentsAction::ShowStats => Action::ShowStats,
             TorrentsAction::ChangeCategory => Action::ChangeCategory,
+            TorrentsAction::AddLabel => Action::AddLabel,
+            TorrentsAction::RemoveLabel => Action::RemoveLabel,
+            TorrentsAction::VisualSelect => Action::VisualSelect,
+            TorrentsAction::SelectAll => Action::SelectAll,
+            TorrentsAction::InvertSelection => Action::InvertSelection,
             TorrentsAction::Rename => Action::Rename,
+            TorrentsAction::EditColumns => Action::EditColumns,
+            TorrentsAction::CycleGrouping => Action::CycleGrouping,
+            TorrentsAction::ToggleGroups => Action::ToggleGroups,
         }
     }
 }
@@ -5,12 +5,18 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TorrentsFileViewerAction {
     ChangeFilePriority,
+    Rename,
+    BatchRename,
+    SelectByGlob,
 }
 
 impl UserAction for TorrentsFileViewerAction {
     fn desc(&self) -> &'static str {
         match self {
             TorrentsFileViewerAction::ChangeFilePriority => "change file priority",
+            TorrentsFileViewerAction::Rename => "rename file or directory",
+            TorrentsFileViewerAction::BatchRename => "rename files using a regex",
+            TorrentsFileViewerAction::SelectByGlob => "toggle or prioritize files by glob",
         }
     }
 }
@@ -19,6 +25,9 @@ impl From<TorrentsFileViewerAction> for Action {
     fn from(value: TorrentsFileViewerAction) -> Self {
         match value {
             TorrentsFileViewerAction::ChangeFilePriority => Action::ChangeFilePriority,
+            TorrentsFileViewerAction::Rename => Action::Rename,
+            TorrentsFileViewerAction::BatchRename => Action::BatchRename,
+            TorrentsFileViewerAction::SelectByGlob => Action::SelectByGlob,
         }
     }
 }
@@ -13,7 +13,8 @@ use serde::Deserialize;
 use rm_shared::action::Action;
 
 pub use self::actions::{
-    general::GeneralAction, search_tab::SearchAction, torrents_tab::TorrentsAction,
+    general::GeneralAction, input::InputAction, search_tab::SearchAction,
+    torrents_tab::TorrentsAction,
 };
 
 #[derive(Deserialize, Clone)]
@@ -23,6 +24,20 @@ pub struct KeymapConfig {
     #[serde(default = "default_torrents_tab_file_viewer")]
     pub torrents_tab_file_viewer: KeybindsHolder<TorrentsFileViewerAction, Action>,
     pub search_tab: KeybindsHolder<SearchAction, Action>,
+    #[serde(default = "default_input")]
+    pub input: KeybindsHolder<InputAction>,
+}
+
+// Keymaps created before [input] existed get the bundled bindings.
+fn default_input() -> KeybindsHolder<InputAction> {
+    #[derive(Deserialize)]
+    struct Defaults {
+        input: KeybindsHolder<InputAction>,
+    }
+
+    toml::from_str::<Defaults>(KeymapConfig::default_config())
+        .expect("default keymap is valid")
+        .input
 }
 
 fn default_torrents_tab_file_viewer() -> KeybindsHolder<TorrentsFileViewerAction, Action> {
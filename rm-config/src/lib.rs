@@ -1,15 +1,20 @@
 pub mod categories;
+pub mod column_layout;
 pub mod keymap;
 pub mod logging;
 pub mod main_config;
+pub mod rules;
+pub mod theme;
 
 use std::{path::PathBuf, sync::LazyLock};
 
 use categories::CategoriesConfig;
-use color_eyre::Result;
+use color_eyre::{eyre::WrapErr, Result};
 use intuitils::config::IntuiConfig;
 use keymap::KeymapConfig;
 use main_config::MainConfig;
+use rules::RulesConfig;
+use theme::Theme;
 
 pub static CONFIG: LazyLock<Config> = LazyLock::new(|| {
     Config::init().unwrap_or_else(|e| {
@@ -24,8 +29,10 @@ pub struct Config {
     pub torrents_tab: main_config::TorrentsTab,
     pub search_tab: main_config::SearchTab,
     pub icons: main_config::Icons,
+    pub theme: Theme,
     pub keybindings: KeymapConfig,
     pub categories: CategoriesConfig,
+    pub rules: RulesConfig,
     pub directories: Directories,
 }
 
@@ -33,18 +40,28 @@ pub struct Directories {
     pub main_path: &'static PathBuf,
     pub keymap_path: &'static PathBuf,
     pub categories_path: &'static PathBuf,
+    pub rules_path: &'static PathBuf,
 }
 
 impl Config {
     fn init() -> Result<Self> {
-        let main_config = MainConfig::init()?;
+        let mut main_config = MainConfig::init()?;
+        main_config
+            .torrents_tab
+            .validate()
+            .wrap_err_with(|| format!("Invalid config located at {:?}", MainConfig::path()))?;
+        let theme = Theme::load(&main_config.general.theme, main_config.general.accent_color)
+            .wrap_err_with(|| format!("Invalid config located at {:?}", MainConfig::path()))?;
+        main_config.torrents_tab.apply_theme(&theme);
         let keybindings = KeymapConfig::init()?;
         let categories = CategoriesConfig::init()?;
+        let rules = RulesConfig::init()?;
 
         let directories = Directories {
             main_path: MainConfig::path(),
             keymap_path: KeymapConfig::path(),
             categories_path: CategoriesConfig::path(),
+            rules_path: RulesConfig::path(),
         };
 
         Ok(Self {
@@ -53,8 +70,10 @@ impl Config {
             torrents_tab: main_config.torrents_tab,
             search_tab: main_config.search_tab,
             icons: main_config.icons,
+            theme,
             keybindings,
             categories,
+            rules,
             directories,
         })
     }
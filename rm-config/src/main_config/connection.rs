@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use url::Url;
+use url::{Host, Url};
 
 #[derive(Deserialize)]
 pub struct Connection {
@@ -12,6 +12,48 @@ pub struct Connection {
     pub stats_refresh: u64,
     #[serde(default = "default_refresh")]
     pub free_space_refresh: u64,
+    #[serde(default)]
+    pub path_mappings: Vec<PathMapping>,
+}
+
+/// Where a directory of the daemon's machine is reachable locally, e.g. over
+/// NFS or SMB.
+#[derive(Deserialize)]
+pub struct PathMapping {
+    pub remote: String,
+    pub local: String,
+}
+
+impl Connection {
+    /// Translates a path on the daemon's machine to a local one using the
+    /// longest matching `path_mappings` prefix.
+    pub fn local_path(&self, remote_path: &str) -> String {
+        self.mapped_local_path(remote_path)
+            .unwrap_or_else(|| remote_path.to_string())
+    }
+
+    /// Like `local_path`, but only if some mapping matched.
+    pub fn mapped_local_path(&self, remote_path: &str) -> Option<String> {
+        self.path_mappings
+            .iter()
+            .filter_map(|mapping| {
+                let remote = mapping.remote.trim_end_matches('/');
+                let rest = remote_path.strip_prefix(remote)?;
+                (rest.is_empty() || rest.starts_with('/')).then_some((remote.len(), mapping, rest))
+            })
+            .max_by_key(|(len, _, _)| *len)
+            .map(|(_, mapping, rest)| format!("{}{rest}", mapping.local.trim_end_matches('/')))
+    }
+
+    /// Whether the daemon runs on this machine, so that its paths are local.
+    pub fn is_local(&self) -> bool {
+        match self.url.host() {
+            Some(Host::Domain(domain)) => domain == "localhost",
+            Some(Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        }
+    }
 }
 
 fn default_refresh() -> u64 {
@@ -7,10 +7,38 @@ pub struct General {
     pub auto_hide: bool,
     #[serde(default = "default_accent_color")]
     pub accent_color: Color,
+    #[serde(default = "default_theme")]
+    pub theme: String,
     #[serde(default = "default_beginner_mode")]
     pub beginner_mode: bool,
     #[serde(default)]
     pub headers_hide: bool,
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+    #[serde(default)]
+    pub openers: Vec<Opener>,
+}
+
+/// Command used to open files with one of the given extensions instead of
+/// the system's default application.
+#[derive(Deserialize)]
+pub struct Opener {
+    pub extensions: Vec<String>,
+    pub command: Vec<String>,
+}
+
+impl General {
+    pub fn opener_for(&self, path: &str) -> Option<&Opener> {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let (_, extension) = name.rsplit_once('.')?;
+
+        self.openers.iter().find(|opener| {
+            opener
+                .extensions
+                .iter()
+                .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(extension))
+        })
+    }
 }
 
 impl Default for General {
@@ -18,8 +46,11 @@ impl Default for General {
         Self {
             auto_hide: false,
             accent_color: default_accent_color(),
+            theme: default_theme(),
             beginner_mode: default_beginner_mode(),
             headers_hide: false,
+            mouse: default_mouse(),
+            openers: vec![],
         }
     }
 }
@@ -28,6 +59,14 @@ fn default_accent_color() -> Color {
     Color::LightMagenta
 }
 
+fn default_theme() -> String {
+    "dark".to_string()
+}
+
 fn default_beginner_mode() -> bool {
     true
 }
+
+fn default_mouse() -> bool {
+    true
+}
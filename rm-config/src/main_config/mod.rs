@@ -4,12 +4,15 @@ mod icons;
 mod search_tab;
 mod torrents_tab;
 
-pub use connection::Connection;
-pub use general::General;
+pub use connection::{Connection, PathMapping};
+pub use general::{General, Opener};
 pub use icons::Icons;
 use intuitils::config::IntuiConfig;
 pub use search_tab::SearchTab;
-pub use torrents_tab::TorrentsTab;
+pub use torrents_tab::{
+    CustomHeader, CustomHeaderFormat, CustomHeaderKind, GroupBy, RowModifier, RowStatus, RowStyle,
+    TorrentsTab,
+};
 
 use serde::Deserialize;
 
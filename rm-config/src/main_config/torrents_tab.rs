@@ -1,16 +1,259 @@
-use rm_shared::header::Header;
-use serde::Deserialize;
+use color_eyre::{eyre::eyre, Result};
+use ratatui::style::{Color, Modifier, Style};
+use regex::Regex;
+use rm_shared::{expression::Expression, glob::Glob, header::Header};
+use serde::{de, Deserialize, Deserializer};
+
+use crate::theme::Theme;
 
 #[derive(Deserialize)]
 pub struct TorrentsTab {
     #[serde(default = "default_headers")]
     pub headers: Vec<Header>,
+    /// Widths of `headers`, in the same order. 0 keeps a header's default width.
+    #[serde(default)]
+    pub header_widths: Vec<u16>,
     #[serde(default = "default_sort")]
     pub default_sort: Header,
     #[serde(default = "default_true")]
     pub default_sort_reverse: bool,
+    /// Groups torrents into collapsible groups from the start.
+    #[serde(default)]
+    pub group_by: Option<GroupBy>,
     #[serde(default = "default_true")]
     pub category_icon_insert_into_name: bool,
+    #[serde(default)]
+    pub unwanted_files: Vec<Glob>,
+    #[serde(default)]
+    pub directory_bookmarks: Vec<String>,
+    #[serde(default)]
+    pub custom_headers: Vec<CustomHeader>,
+    #[serde(default, rename = "row_styles")]
+    configured_row_styles: Option<Vec<RowStyle>>,
+    /// Configured row styles, or the default ones in the theme's colors.
+    #[serde(skip)]
+    pub row_styles: Vec<RowStyle>,
+}
+
+/// Style applied to every torrent row matching all of the given predicates.
+/// Rules are checked in order and the first matching one wins.
+#[derive(Deserialize)]
+pub struct RowStyle {
+    #[serde(default)]
+    pub status: Vec<RowStatus>,
+    pub min_ratio: Option<f32>,
+    pub max_ratio: Option<f32>,
+    pub category: Option<String>,
+    pub stalled: Option<bool>,
+    pub private: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_age")]
+    pub min_age: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_age")]
+    pub max_age: Option<i64>,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    #[serde(default)]
+    pub modifiers: Vec<RowModifier>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RowStatus {
+    Error,
+    Stopped,
+    QueuedToVerify,
+    Verifying,
+    QueuedToDownload,
+    Downloading,
+    QueuedToSeed,
+    Seeding,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Category,
+    DownloadDir,
+    Tracker,
+    Status,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum RowModifier {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    SlowBlink,
+    Reversed,
+    CrossedOut,
+}
+
+impl From<RowModifier> for Modifier {
+    fn from(modifier: RowModifier) -> Self {
+        match modifier {
+            RowModifier::Bold => Modifier::BOLD,
+            RowModifier::Dim => Modifier::DIM,
+            RowModifier::Italic => Modifier::ITALIC,
+            RowModifier::Underlined => Modifier::UNDERLINED,
+            RowModifier::SlowBlink => Modifier::SLOW_BLINK,
+            RowModifier::Reversed => Modifier::REVERSED,
+            RowModifier::CrossedOut => Modifier::CROSSED_OUT,
+        }
+    }
+}
+
+impl RowStyle {
+    pub fn style(&self) -> Style {
+        let mut style = Style::default();
+        if let Some(fg) = self.fg {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg);
+        }
+        for modifier in &self.modifiers {
+            style = style.add_modifier(Modifier::from(*modifier));
+        }
+        style
+    }
+}
+
+// Accepts either plain seconds or a number with one of s, m, h, d, w suffixes.
+fn deserialize_age<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Age {
+        Seconds(i64),
+        Human(String),
+    }
+
+    let age = match Age::deserialize(deserializer)? {
+        Age::Seconds(seconds) => seconds,
+        Age::Human(age) => {
+            let age = age.trim();
+            let (number, unit) =
+                age.split_at(age.find(|c: char| !c.is_ascii_digit()).unwrap_or(age.len()));
+            let multiplier = match unit.trim() {
+                "" | "s" => 1,
+                "m" => 60,
+                "h" => 60 * 60,
+                "d" => 60 * 60 * 24,
+                "w" => 60 * 60 * 24 * 7,
+                unit => {
+                    return Err(de::Error::custom(format!(
+                        "unknown age unit \"{unit}\", use one of s, m, h, d, w"
+                    )))
+                }
+            };
+            let number: i64 = number
+                .parse()
+                .map_err(|_| de::Error::custom(format!("invalid age \"{age}\"")))?;
+            number * multiplier
+        }
+    };
+
+    Ok(Some(age))
+}
+
+#[derive(Deserialize)]
+#[serde(try_from = "RawCustomHeader")]
+pub struct CustomHeader {
+    pub name: String,
+    pub width: Option<u16>,
+    pub kind: CustomHeaderKind,
+}
+
+pub enum CustomHeaderKind {
+    Expression {
+        expr: Expression,
+        format: CustomHeaderFormat,
+    },
+    /// Shows the first label matching the regex.
+    Label(Regex),
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomHeaderFormat {
+    #[default]
+    Number,
+    Bytes,
+    Speed,
+    Percent,
+    Duration,
+    Date,
+}
+
+#[derive(Deserialize)]
+struct RawCustomHeader {
+    name: String,
+    expr: Option<Expression>,
+    label_regex: Option<String>,
+    #[serde(default)]
+    format: CustomHeaderFormat,
+    width: Option<u16>,
+}
+
+impl TryFrom<RawCustomHeader> for CustomHeader {
+    type Error = String;
+
+    fn try_from(raw: RawCustomHeader) -> Result<Self, Self::Error> {
+        let kind = match (raw.expr, raw.label_regex) {
+            (Some(expr), None) => CustomHeaderKind::Expression {
+                expr,
+                format: raw.format,
+            },
+            (None, Some(label_regex)) => CustomHeaderKind::Label(
+                Regex::new(&label_regex)
+                    .map_err(|e| format!("invalid label_regex in \"{}\": {e}", raw.name))?,
+            ),
+            _ => {
+                return Err(format!(
+                    "custom header \"{}\" needs exactly one of `expr` or `label_regex`",
+                    raw.name
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: raw.name,
+            width: raw.width,
+            kind,
+        })
+    }
+}
+
+impl TorrentsTab {
+    pub fn header_name<'a>(&'a self, header: &Header) -> &'a str {
+        match header {
+            Header::Custom(idx) => &self.custom_headers[*idx].name,
+            header => header.header_name(),
+        }
+    }
+
+    pub(crate) fn apply_theme(&mut self, theme: &Theme) {
+        self.row_styles = self
+            .configured_row_styles
+            .take()
+            .unwrap_or_else(|| default_row_styles(theme));
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        for header in self.headers.iter().chain([&self.default_sort]) {
+            if let Header::Custom(idx) = header {
+                if *idx >= self.custom_headers.len() {
+                    return Err(eyre!(
+                        "torrents_tab: Custom header {idx} is used, but only {} custom_headers are defined",
+                        self.custom_headers.len()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 fn default_true() -> bool {
@@ -21,6 +264,27 @@ fn default_sort() -> Header {
     Header::AddedDate
 }
 
+fn default_row_styles(theme: &Theme) -> Vec<RowStyle> {
+    let rule = |status, fg, modifiers| RowStyle {
+        status: vec![status],
+        min_ratio: None,
+        max_ratio: None,
+        category: None,
+        stalled: None,
+        private: None,
+        min_age: None,
+        max_age: None,
+        fg: Some(fg),
+        bg: None,
+        modifiers,
+    };
+
+    vec![
+        rule(RowStatus::Error, theme.error, vec![]),
+        rule(RowStatus::Stopped, theme.stopped, vec![RowModifier::Italic]),
+    ]
+}
+
 fn default_headers() -> Vec<Header> {
     vec![
         Header::Name,
@@ -36,9 +300,40 @@ impl Default for TorrentsTab {
     fn default() -> Self {
         Self {
             headers: default_headers(),
+            header_widths: vec![],
             default_sort: default_sort(),
             default_sort_reverse: default_true(),
+            group_by: None,
             category_icon_insert_into_name: default_true(),
+            unwanted_files: vec![],
+            directory_bookmarks: vec![],
+            custom_headers: vec![],
+            configured_row_styles: None,
+            row_styles: vec![],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_age(age: &str) -> Result<Option<i64>, toml::de::Error> {
+        toml::from_str::<RowStyle>(&format!("max_age = {age}")).map(|style| style.max_age)
+    }
+
+    #[test]
+    fn ages_accept_units() {
+        assert_eq!(max_age("90").unwrap(), Some(90));
+        assert_eq!(max_age("\"90\"").unwrap(), Some(90));
+        assert_eq!(max_age("\"30m\"").unwrap(), Some(30 * 60));
+        assert_eq!(max_age("\"12 h\"").unwrap(), Some(12 * 60 * 60));
+        assert_eq!(max_age("\"2w\"").unwrap(), Some(2 * 7 * 24 * 60 * 60));
+    }
+
+    #[test]
+    fn invalid_ages_are_rejected() {
+        assert!(max_age("\"2y\"").is_err());
+        assert!(max_age("\"d\"").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use intuitils::config::IntuiConfig;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+pub struct RulesConfig {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl IntuiConfig for RulesConfig {
+    fn app_name() -> &'static str {
+        "rustmission"
+    }
+
+    fn filename() -> &'static str {
+        "rules.toml"
+    }
+
+    fn default_config() -> &'static str {
+        include_str!("../defaults/rules.toml")
+    }
+
+    fn should_exit_if_not_found() -> bool {
+        false
+    }
+
+    fn message_if_not_found() -> Option<String> {
+        None
+    }
+}
+
+/// Assigns a category (and optionally a download dir) to new torrents that
+/// match all of the conditions that are set.
+#[derive(Deserialize)]
+#[serde(try_from = "RawRule")]
+pub struct Rule {
+    pub name_regex: Option<Regex>,
+    pub tracker_host: Option<String>,
+    pub extensions: Vec<String>,
+    pub min_size: Option<i64>,
+    pub max_size: Option<i64>,
+    pub category: String,
+    pub download_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawRule {
+    name_regex: Option<String>,
+    tracker_host: Option<String>,
+    #[serde(default)]
+    extensions: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_size")]
+    min_size: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_size")]
+    max_size: Option<i64>,
+    category: String,
+    download_dir: Option<String>,
+}
+
+impl TryFrom<RawRule> for Rule {
+    type Error = String;
+
+    fn try_from(raw: RawRule) -> Result<Self, Self::Error> {
+        if raw.name_regex.is_none()
+            && raw.tracker_host.is_none()
+            && raw.extensions.is_empty()
+            && raw.min_size.is_none()
+            && raw.max_size.is_none()
+        {
+            return Err(format!(
+                "rule for category \"{}\" needs at least one condition",
+                raw.category
+            ));
+        }
+
+        let name_regex = raw
+            .name_regex
+            .map(|regex| Regex::new(&regex))
+            .transpose()
+            .map_err(|e| format!("invalid name_regex for category \"{}\": {e}", raw.category))?;
+
+        let extensions = raw
+            .extensions
+            .into_iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect();
+
+        Ok(Self {
+            name_regex,
+            tracker_host: raw.tracker_host.map(|host| host.to_lowercase()),
+            extensions,
+            min_size: raw.min_size,
+            max_size: raw.max_size,
+            category: raw.category,
+            download_dir: raw.download_dir,
+        })
+    }
+}
+
+impl Rule {
+    /// Whether the rule looks at files or size, which magnets lack until
+    /// their metadata is fetched.
+    pub fn needs_metadata(&self) -> bool {
+        !self.extensions.is_empty() || self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    pub fn matches(
+        &self,
+        name: &str,
+        tracker_hosts: &[String],
+        files: &[String],
+        size: i64,
+    ) -> bool {
+        self.name_regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(name))
+            && self.tracker_host.as_ref().is_none_or(|wanted| {
+                tracker_hosts.iter().any(|host| {
+                    let host = host.to_lowercase();
+                    host == *wanted || host.ends_with(&format!(".{wanted}"))
+                })
+            })
+            && (self.extensions.is_empty()
+                || files.iter().any(|file| {
+                    let file = file.to_lowercase();
+                    self.extensions
+                        .iter()
+                        .any(|ext| file.ends_with(&format!(".{ext}")))
+                }))
+            && self.min_size.is_none_or(|min_size| size >= min_size)
+            && self.max_size.is_none_or(|max_size| size <= max_size)
+    }
+}
+
+impl RulesConfig {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether this label is a category that some rule assigns.
+    pub fn assigns_category(&self, label: &str) -> bool {
+        self.rules.iter().any(|rule| rule.category == label)
+    }
+}
+
+// Accepts either plain bytes or a number with a unit, e.g. "700 MB" or "4GiB".
+fn deserialize_size<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Size {
+        Bytes(i64),
+        Human(String),
+    }
+
+    let size = match Size::deserialize(deserializer)? {
+        Size::Bytes(bytes) => bytes,
+        Size::Human(size) => {
+            let size = size.trim();
+            let split_at = size
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(size.len());
+            let (number, unit) = size.split_at(split_at);
+            let number: f64 = number
+                .parse()
+                .map_err(|_| de::Error::custom(format!("invalid size \"{size}\"")))?;
+            let multiplier: f64 = match unit.trim().to_lowercase().as_str() {
+                "" | "b" => 1.0,
+                "kb" => 1e3,
+                "mb" => 1e6,
+                "gb" => 1e9,
+                "tb" => 1e12,
+                "kib" => 1024.0,
+                "mib" => 1024.0 * 1024.0,
+                "gib" => 1024.0 * 1024.0 * 1024.0,
+                "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+                unit => {
+                    return Err(de::Error::custom(format!(
+                        "unknown size unit \"{unit}\", use one of B, KB, MB, GB, TB, KiB, MiB, GiB, TiB"
+                    )))
+                }
+            };
+            (number * multiplier) as i64
+        }
+    };
+
+    Ok(Some(size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(conditions: &str) -> Result<Rule, toml::de::Error> {
+        toml::from_str::<RulesConfig>(&format!("[[rules]]\ncategory = \"Test\"\n{conditions}"))
+            .map(|config| config.rules.into_iter().next().unwrap())
+    }
+
+    #[test]
+    fn sizes_accept_units() {
+        let size = |size: &str| rule(&format!("min_size = {size}")).unwrap().min_size;
+
+        assert_eq!(size("700"), Some(700));
+        assert_eq!(size("\"700\""), Some(700));
+        assert_eq!(size("\"700 MB\""), Some(700_000_000));
+        assert_eq!(size("\"1.5kb\""), Some(1500));
+        assert_eq!(size("\"4GiB\""), Some(4 * 1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn invalid_sizes_are_rejected() {
+        assert!(rule("min_size = \"4 GiBs\"").is_err());
+        assert!(rule("min_size = \"GB\"").is_err());
+        assert!(rule("min_size = \"1.2.3 MB\"").is_err());
+    }
+
+    #[test]
+    fn size_bounds_are_inclusive() {
+        let rule = rule("min_size = \"1 KB\"\nmax_size = \"2 KB\"").unwrap();
+
+        assert!(!rule.matches("", &[], &[], 999));
+        assert!(rule.matches("", &[], &[], 1000));
+        assert!(rule.matches("", &[], &[], 2000));
+        assert!(!rule.matches("", &[], &[], 2001));
+    }
+
+    #[test]
+    fn rule_without_conditions_is_rejected() {
+        assert!(rule("").is_err());
+    }
+}
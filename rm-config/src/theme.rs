@@ -0,0 +1,109 @@
+use std::{fs, io, path::PathBuf};
+
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use intuitils::config::IntuiConfig;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::main_config::MainConfig;
+
+/// How many themes can extend one another before it's deemed a loop.
+const MAX_BASE_DEPTH: u8 = 8;
+
+const BUNDLED: [(&str, &str); 4] = [
+    ("dark", include_str!("../defaults/themes/dark.toml")),
+    ("light", include_str!("../defaults/themes/light.toml")),
+    (
+        "solarized",
+        include_str!("../defaults/themes/solarized.toml"),
+    ),
+    (
+        "high-contrast",
+        include_str!("../defaults/themes/high-contrast.toml"),
+    ),
+];
+
+/// Colors of everything rustmission draws.
+///
+/// Themes are picked with `theme` in config.toml, either from the bundled ones
+/// or from `themes/<name>.toml` next to it. A theme file may set only some
+/// colors and take the rest from `base`, which is "dark" if not given.
+#[derive(Deserialize, Clone)]
+pub struct Theme {
+    pub accent: Color,
+    pub text: Color,
+    pub dim: Color,
+    pub highlight_bg: Color,
+    pub border: Color,
+    pub inactive: Color,
+    pub stopped: Color,
+    pub error: Color,
+    pub warning: Color,
+    pub success: Color,
+    pub seeders: Color,
+}
+
+impl Theme {
+    /// Loads the named theme. `accent_color` is used if it doesn't set an accent.
+    pub(crate) fn load(name: &str, accent_color: Color) -> Result<Self> {
+        let mut table = load_table(name, true, 0)?;
+        if !table.contains_key("accent") {
+            table.insert("accent".to_string(), toml::Value::try_from(accent_color)?);
+        }
+
+        table
+            .try_into()
+            .wrap_err_with(|| format!("Invalid theme \"{name}\""))
+    }
+}
+
+/// Contents of a theme merged over those of its base.
+fn load_table(name: &str, user_files: bool, depth: u8) -> Result<toml::Table> {
+    if depth > MAX_BASE_DEPTH {
+        bail!("Theme \"{name}\" is based on itself");
+    }
+
+    let path = themes_dir().join(format!("{name}.toml"));
+    let user_contents = if user_files {
+        match fs::read_to_string(&path) {
+            Ok(contents) => Some(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {path:?}")),
+        }
+    } else {
+        None
+    };
+    let is_user_theme = user_contents.is_some();
+
+    let mut table: toml::Table = match user_contents {
+        Some(contents) => {
+            toml::from_str(&contents).wrap_err_with(|| format!("Invalid theme at {path:?}"))?
+        }
+        None => match BUNDLED.iter().find(|(bundled, _)| *bundled == name) {
+            Some((_, contents)) => toml::from_str(contents)?,
+            None => bail!("Theme \"{name}\" is neither bundled nor found at {path:?}"),
+        },
+    };
+
+    let base = match table.remove("base") {
+        Some(toml::Value::String(base)) => base,
+        Some(_) => bail!("{path:?}: base has to be a name of a theme"),
+        None if name == "dark" && !is_user_theme => return Ok(table),
+        None => "dark".to_string(),
+    };
+
+    // A user theme named like a bundled one can build upon it.
+    let mut merged = load_table(&base, base != name, depth + 1)?;
+    merged.extend(table);
+    Ok(merged)
+}
+
+fn themes_dir() -> PathBuf {
+    MainConfig::path()
+        .parent()
+        .map(|dir| dir.join("themes"))
+        .unwrap_or_default()
+}
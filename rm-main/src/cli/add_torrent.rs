@@ -1,8 +1,11 @@
-use std::{fs::File, io::Read};
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+};
 
 use base64::Engine;
 use color_eyre::Result;
-use transmission_rpc::types::TorrentAddArgs;
+use transmission_rpc::types::{Id, TorrentAddArgs, TorrentSetArgs};
 
 use crate::transmission;
 
@@ -34,13 +37,59 @@ pub(super) async fn add_torrent(torrent: String) -> Result<()> {
         }
     };
 
-    if let Err(e) = transclient.torrent_add(args).await {
-        eprintln!("error while adding a torrent: {e}");
-        if e.to_string().contains("expected value at line") {
-            eprintln!("Check whether your arguments are valid.");
+    match transmission::duplicates::find_duplicate(&mut transclient, &args).await {
+        Ok(Some(duplicate)) => {
+            eprintln!(
+                "this torrent has already been added as \"{}\" (ID {})",
+                duplicate.name, duplicate.id
+            );
+            if !duplicate.new_trackers.is_empty() && confirm_merge(duplicate.new_trackers.len())? {
+                let args = TorrentSetArgs::default()
+                    .tracker_list(transmission::duplicates::merged_tracker_list(&duplicate));
+                if let Err(e) = transclient
+                    .torrent_set(args, Some(vec![Id::Id(duplicate.id)]))
+                    .await
+                {
+                    eprintln!("error while merging trackers: {e}");
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
         }
+        Ok(None) => (),
+        Err(e) => eprintln!("couldn't check whether the torrent was already added: {e}"),
+    }
+
+    let added = match transmission::categories::add_torrent(&mut transclient, args).await {
+        Ok(added) => added,
+        Err(e) => {
+            eprintln!("error while adding a torrent: {e}");
+            if e.to_string().contains("expected value at line") {
+                eprintln!("Check whether your arguments are valid.");
+            }
 
-        std::process::exit(1);
+            std::process::exit(1);
+        }
     };
+
+    if let Some(e) = added.policy_error {
+        eprintln!("the torrent was added, but the policy of its category couldn't be applied: {e}");
+    }
+
+    if let Some(id) = added.id {
+        if let Err(e) = transmission::auto_categorize::categorize_added(&mut transclient, id).await
+        {
+            eprintln!("error while categorizing the torrent: {e}");
+        }
+    }
     Ok(())
 }
+
+fn confirm_merge(new_trackers: usize) -> Result<bool> {
+    print!("Merge {new_trackers} new tracker(s) into it? [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
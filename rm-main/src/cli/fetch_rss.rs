@@ -35,9 +35,22 @@ pub async fn fetch_rss(url: &str, filter: Option<&str>) -> Result<()> {
             filename: Some(url.to_string()),
             ..Default::default()
         };
-        if let Err(e) = transclient.torrent_add(args).await {
-            tracing::error!("RSS: cannot download {title} from {url} {e}");
-            bail!("error while adding a torrent: {e}")
+        let added = match transmission::categories::add_torrent(&mut transclient, args).await {
+            Ok(added) => added,
+            Err(e) => {
+                tracing::error!("RSS: cannot download {title} from {url} {e}");
+                bail!("error while adding a torrent: {e}")
+            }
+        };
+        if let Some(e) = added.policy_error {
+            tracing::error!("RSS: cannot apply the category policy to {title}: {e}");
+        }
+        if let Some(id) = added.id {
+            if let Err(e) =
+                transmission::auto_categorize::categorize_added(&mut transclient, id).await
+            {
+                tracing::error!("RSS: cannot categorize {title}: {e}");
+            }
         }
     }
     Ok(())
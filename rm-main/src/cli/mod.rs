@@ -22,6 +22,7 @@ pub enum Commands {
     PrintDefaultConfig {},
     PrintDefaultKeymap {},
     PrintDefaultCategories {},
+    PrintDefaultRules {},
 }
 
 pub async fn handle_command(command: Commands) -> Result<()> {
@@ -51,6 +52,10 @@ pub async fn handle_command(command: Commands) -> Result<()> {
                 rm_config::categories::CategoriesConfig::default_config()
             )
         }
+        Commands::PrintDefaultRules {} => {
+            tracing::info!("Printing auto-categorization rules");
+            println!("{}", rm_config::rules::RulesConfig::default_config())
+        }
     }
     Ok(())
 }
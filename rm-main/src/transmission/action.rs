@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
@@ -12,21 +12,36 @@ use transmission_rpc::TransClient;
 use rm_shared::action::ErrorMessage;
 use rm_shared::action::UpdateAction;
 
+use super::{
+    categories::{self, AddTorrentError, AddedTorrent},
+    duplicates,
+};
+
 const FAILED_TO_COMMUNICATE: &str = "Failed to communicate with Transmission";
 
 pub enum TorrentAction {
     // Add a torrent with this Magnet/URL, Directory, Label (Category)
     Add(String, Option<String>, Option<String>),
+    // Add a torrent with these arguments, Name to report it with
+    AddWithArgs(String, Box<TorrentAddArgs>),
+    // Add torrents with these arguments, Names to report them with (one status task for all)
+    AddAllWithArgs(Vec<(String, Box<TorrentAddArgs>)>),
     // Stop Torrents with these given IDs
     Stop(Vec<Id>),
     // Start Torrents with these given IDs
     Start(Vec<Id>),
     // Torrent ID, Directory to move to
     Move(Vec<Id>, String),
-    // Torrent ID, Current name, Name to change to
+    // Torrent ID, Current path (relative to the download dir), Name to change to
     Rename(Id, String, String),
     // Torrent ID, Category to set
     ChangeCategory(Vec<Id>, String),
+    // Torrent ID, Category to assign, Directory to move to (no status task)
+    AssignCategory(Vec<Id>, String, Option<String>),
+    // Torrent ID, Label to add
+    AddLabel(Vec<Id>, String),
+    // Torrent ID, Label to remove
+    RemoveLabel(Vec<Id>, String),
     // Delete Torrents with these given IDs (without files)
     DelWithoutFiles(Vec<Id>),
     // Delete Torrents with these given IDs (with files)
@@ -72,14 +87,84 @@ pub async fn action_handler(
                     labels: label,
                     ..Default::default()
                 };
-                match client.torrent_add(args).await {
-                    Ok(_) => {
+                if report_duplicate(&mut client, &args, &update_tx).await {
+                    continue;
+                }
+                match categories::add_torrent(&mut client, args).await {
+                    Ok(added) => {
                         update_tx.send(UpdateAction::StatusTaskSuccess).unwrap();
+                        report_policy_error(added, &update_tx);
                     }
                     Err(err) => {
                         let msg = format!("Failed to add torrent with URL/Path: \"{url}\"");
-                        tracing::error!("{msg}");
-                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                        tracing::error!("{msg}: {err}");
+                        let err_message = ErrorMessage::new(add_error_title(&*err), msg, err);
+                        update_tx
+                            .send(UpdateAction::Error(Box::new(err_message)))
+                            .unwrap();
+                        update_tx.send(UpdateAction::StatusTaskFailure).unwrap();
+                    }
+                }
+            }
+            TorrentAction::AddWithArgs(name, args) => {
+                if report_duplicate(&mut client, &args, &update_tx).await {
+                    continue;
+                }
+                match categories::add_torrent(&mut client, *args).await {
+                    Ok(added) => {
+                        update_tx.send(UpdateAction::StatusTaskSuccess).unwrap();
+                        report_policy_error(added, &update_tx);
+                    }
+                    Err(err) => {
+                        let msg = format!("Failed to add torrent \"{name}\"");
+                        tracing::error!("{msg}: {err}");
+                        let err_message = ErrorMessage::new(add_error_title(&*err), msg, err);
+                        update_tx
+                            .send(UpdateAction::Error(Box::new(err_message)))
+                            .unwrap();
+                        update_tx.send(UpdateAction::StatusTaskFailure).unwrap();
+                    }
+                }
+            }
+            TorrentAction::AddAllWithArgs(torrents) => {
+                let total = torrents.len();
+                let mut failures = vec![];
+                let mut duplicate = None;
+
+                for (name, args) in torrents {
+                    match duplicates::find_duplicate(&mut client, &args).await {
+                        Ok(Some(found)) => {
+                            tracing::info!("Torrent \"{name}\" already exists, ID={}", found.id);
+                            duplicate.get_or_insert(found);
+                            continue;
+                        }
+                        Ok(None) => (),
+                        Err(err) => tracing::warn!("Failed to look for a duplicate torrent: {err}"),
+                    }
+
+                    match categories::add_torrent(&mut client, *args).await {
+                        Ok(added) => report_policy_error(added, &update_tx),
+                        Err(err) => {
+                            tracing::error!("Failed to add torrent \"{name}\": {err}");
+                            failures.push((name, err));
+                        }
+                    }
+                }
+
+                if let Some(duplicate) = duplicate {
+                    update_tx
+                        .send(UpdateAction::DuplicateTorrent(Box::new(duplicate)))
+                        .unwrap();
+                }
+
+                match failures.pop() {
+                    None => update_tx.send(UpdateAction::StatusTaskSuccess).unwrap(),
+                    Some((name, err)) => {
+                        let msg = format!(
+                            "Failed to add {} of {total} torrents, last one was \"{name}\"",
+                            failures.len() + 1
+                        );
+                        let err_message = ErrorMessage::new(add_error_title(&*err), msg, err);
                         update_tx
                             .send(UpdateAction::Error(Box::new(err_message)))
                             .unwrap();
@@ -89,7 +174,10 @@ pub async fn action_handler(
             }
             TorrentAction::Stop(ids) => {
                 match client.torrent_action(RPCAction::Stop, ids.clone()).await {
-                    Ok(_) => tracing::info!("Stopping torrent, IDs={:?}", ids),
+                    Ok(_) => {
+                        tracing::info!("Stopping torrent, IDs={:?}", ids);
+                        update_tx.send(UpdateAction::StatusTaskSuccess).unwrap();
+                    }
                     Err(err) => {
                         let msg = format!("Failed to stop torrents with these IDs: {:?}", ids);
                         tracing::error!("{msg}");
@@ -97,12 +185,16 @@ pub async fn action_handler(
                         update_tx
                             .send(UpdateAction::Error(Box::new(err_message)))
                             .unwrap();
+                        update_tx.send(UpdateAction::StatusTaskFailure).unwrap();
                     }
                 }
             }
             TorrentAction::Start(ids) => {
                 match client.torrent_action(RPCAction::Start, ids.clone()).await {
-                    Ok(_) => tracing::info!("Starting torrent, IDs={:?}", ids),
+                    Ok(_) => {
+                        tracing::info!("Starting torrent, IDs={:?}", ids);
+                        update_tx.send(UpdateAction::StatusTaskSuccess).unwrap();
+                    }
                     Err(err) => {
                         let msg = format!("Failed to start torrents with these IDs: {:?}", ids);
                         tracing::error!("{msg}");
@@ -110,6 +202,7 @@ pub async fn action_handler(
                         update_tx
                             .send(UpdateAction::Error(Box::new(err_message)))
                             .unwrap();
+                        update_tx.send(UpdateAction::StatusTaskFailure).unwrap();
                     }
                 }
             }
@@ -225,13 +318,7 @@ pub async fn action_handler(
                 }
             }
             TorrentAction::ChangeCategory(ids, category) => {
-                let labels = if category.is_empty() {
-                    vec![]
-                } else {
-                    vec![category]
-                };
-                let args = TorrentSetArgs::default().labels(labels);
-                match client.torrent_set(args, Some(ids)).await {
+                match categories::change_category(&mut client, ids, &category).await {
                     Ok(_) => update_tx.send(UpdateAction::StatusTaskSuccess).unwrap(),
                     Err(err) => {
                         let msg = "Failed to set category";
@@ -244,6 +331,54 @@ pub async fn action_handler(
                     }
                 }
             }
+            TorrentAction::AssignCategory(ids, category, download_dir) => {
+                if let Err(err) =
+                    categories::assign_category(&mut client, ids, &category, download_dir).await
+                {
+                    let msg = format!("Failed to assign category \"{category}\"");
+                    tracing::error!("{msg}");
+                    let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                    update_tx
+                        .send(UpdateAction::Error(Box::new(err_message)))
+                        .unwrap();
+                }
+            }
+            TorrentAction::AddLabel(ids, new_label) => {
+                let add_label = |labels: &mut Vec<String>| {
+                    if !labels.contains(&new_label) {
+                        labels.push(new_label.clone());
+                    }
+                };
+                match edit_labels(&mut client, ids, add_label).await {
+                    Ok(_) => update_tx.send(UpdateAction::StatusTaskSuccess).unwrap(),
+                    Err(err) => {
+                        let msg = format!("Failed to add label \"{new_label}\"");
+                        tracing::error!("{msg}");
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                        update_tx
+                            .send(UpdateAction::Error(Box::new(err_message)))
+                            .unwrap();
+                        update_tx.send(UpdateAction::StatusTaskFailure).unwrap();
+                    }
+                }
+            }
+            TorrentAction::RemoveLabel(ids, old_label) => {
+                let remove_label = |labels: &mut Vec<String>| {
+                    labels.retain(|label| *label != old_label);
+                };
+                match edit_labels(&mut client, ids, remove_label).await {
+                    Ok(_) => update_tx.send(UpdateAction::StatusTaskSuccess).unwrap(),
+                    Err(err) => {
+                        let msg = format!("Failed to remove label \"{old_label}\"");
+                        tracing::error!("{msg}");
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                        update_tx
+                            .send(UpdateAction::Error(Box::new(err_message)))
+                            .unwrap();
+                        update_tx.send(UpdateAction::StatusTaskFailure).unwrap();
+                    }
+                }
+            }
             TorrentAction::Rename(id, current_name, new_name) => {
                 match client
                     .torrent_rename_path(vec![id], current_name, new_name)
@@ -264,3 +399,84 @@ pub async fn action_handler(
         }
     }
 }
+
+// Labels are set per torrent, so that labels that aren't touched by `edit`
+// (e.g. ones set by other tools) are preserved. Torrents ending up with the
+// same labels are set in one go.
+pub(super) async fn edit_labels(
+    client: &mut TransClient,
+    ids: Vec<Id>,
+    edit: impl Fn(&mut Vec<String>),
+) -> transmission_rpc::types::Result<()> {
+    let torrents = client
+        .torrent_get(
+            Some(vec![TorrentGetField::Id, TorrentGetField::Labels]),
+            Some(ids),
+        )
+        .await?
+        .arguments
+        .torrents;
+
+    let mut ids_by_labels: HashMap<Vec<String>, Vec<Id>> = HashMap::new();
+    for torrent in torrents {
+        let Some(id) = torrent.id() else {
+            continue;
+        };
+        let mut labels = torrent.labels.unwrap_or_default();
+        edit(&mut labels);
+        ids_by_labels.entry(labels).or_default().push(id);
+    }
+
+    for (labels, ids) in ids_by_labels {
+        client
+            .torrent_set(TorrentSetArgs::default().labels(labels), Some(ids))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Tells the daemon refusing a torrent apart from it not being reachable.
+fn add_error_title(err: &(dyn std::error::Error + Send + Sync + 'static)) -> &'static str {
+    err.downcast_ref::<AddTorrentError>()
+        .map_or(FAILED_TO_COMMUNICATE, AddTorrentError::title)
+}
+
+/// Reports a category policy that couldn't be applied to an added torrent.
+fn report_policy_error(added: AddedTorrent, update_tx: &UnboundedSender<UpdateAction>) {
+    if let Some(err) = added.policy_error {
+        let msg = format!(
+            "Torrent was added, but the policy of its category couldn't be applied, ID={:?}",
+            added.id
+        );
+        tracing::error!("{msg}: {err}");
+        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+        update_tx
+            .send(UpdateAction::Error(Box::new(err_message)))
+            .unwrap();
+    }
+}
+
+/// Shows the torrent that `args` would duplicate instead of adding it again.
+async fn report_duplicate(
+    client: &mut TransClient,
+    args: &TorrentAddArgs,
+    update_tx: &UnboundedSender<UpdateAction>,
+) -> bool {
+    match duplicates::find_duplicate(client, args).await {
+        Ok(Some(duplicate)) => {
+            tracing::info!("Torrent already exists, ID={}", duplicate.id);
+            update_tx.send(UpdateAction::StatusTaskClear).unwrap();
+            update_tx
+                .send(UpdateAction::DuplicateTorrent(Box::new(duplicate)))
+                .unwrap();
+            true
+        }
+        Ok(None) => false,
+        // Adding goes on, the daemon still refuses real duplicates.
+        Err(err) => {
+            tracing::warn!("Failed to look for a duplicate torrent: {err}");
+            false
+        }
+    }
+}
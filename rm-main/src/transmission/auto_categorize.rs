@@ -0,0 +1,189 @@
+use rm_config::CONFIG;
+use transmission_rpc::{
+    types::{Id, Torrent, TorrentGetField},
+    TransClient,
+};
+
+use crate::tui::ctx::CTX;
+
+use super::{
+    categories,
+    new_torrents::{Handled, NewTorrentHandler},
+    utils::tracker_host,
+    TorrentAction,
+};
+
+/// Category and download dir that a new torrent should get.
+struct Assignment {
+    category: String,
+    download_dir: Option<String>,
+    /// What assigned the category, for the log.
+    reason: String,
+}
+
+enum Decision {
+    Assign(Assignment),
+    NoMatch,
+    /// A rule needing files or size comes first, but the torrent is a magnet
+    /// without metadata yet.
+    AwaitingMetadata,
+}
+
+/// Categorizes torrents that show up uncategorized, by rules from rules.toml
+/// and then by regexes of categories from categories.toml.
+pub fn handler() -> Option<NewTorrentHandler> {
+    if CONFIG.rules.is_empty() && !CONFIG.categories.has_auto_assignment() {
+        return None;
+    }
+
+    Some(NewTorrentHandler {
+        name: "Auto-categorization",
+        wants: |labels| !is_categorized(labels),
+        handle: categorize,
+    })
+}
+
+/// Categorizes a torrent that has just been added outside of the TUI, e.g.
+/// from the CLI or RSS.
+pub async fn categorize_added(
+    client: &mut TransClient,
+    id: Id,
+) -> transmission_rpc::types::Result<()> {
+    let fields = vec![
+        TorrentGetField::Id,
+        TorrentGetField::Name,
+        TorrentGetField::Labels,
+        TorrentGetField::Files,
+        TorrentGetField::TotalSize,
+        TorrentGetField::TrackerStats,
+    ];
+    let Some(torrent) = client
+        .torrent_get(Some(fields), Some(vec![id.clone()]))
+        .await?
+        .arguments
+        .torrents
+        .pop()
+    else {
+        return Ok(());
+    };
+
+    if is_categorized(torrent.labels.as_deref().unwrap_or_default()) {
+        return Ok(());
+    }
+
+    match decide(&torrent) {
+        Decision::Assign(assignment) => {
+            log_assignment(&torrent, &assignment);
+            categories::assign_category(
+                client,
+                vec![id],
+                &assignment.category,
+                assignment.download_dir,
+            )
+            .await
+        }
+        Decision::NoMatch | Decision::AwaitingMetadata => {
+            log_no_match(&torrent);
+            Ok(())
+        }
+    }
+}
+
+fn is_categorized(labels: &[String]) -> bool {
+    labels.iter().any(|label| {
+        CONFIG.categories.map.contains_key(label) || CONFIG.rules.assigns_category(label)
+    })
+}
+
+fn categorize(torrent: &Torrent) -> Handled {
+    let Some(id) = torrent.id() else {
+        return Handled::Done;
+    };
+
+    match decide(torrent) {
+        Decision::Assign(assignment) => {
+            log_assignment(torrent, &assignment);
+            CTX.send_torrent_action(TorrentAction::AssignCategory(
+                vec![id],
+                assignment.category,
+                assignment.download_dir,
+            ));
+        }
+        Decision::NoMatch => log_no_match(torrent),
+        Decision::AwaitingMetadata => return Handled::AwaitingMetadata,
+    }
+
+    Handled::Done
+}
+
+fn decide(torrent: &Torrent) -> Decision {
+    let name = torrent.name.as_deref().unwrap_or_default();
+    let files: Vec<String> = torrent
+        .files
+        .iter()
+        .flatten()
+        .map(|file| file.name.clone())
+        .collect();
+    let size = torrent.total_size.unwrap_or_default();
+    let has_metadata = !files.is_empty() && size != 0;
+
+    let tracker_stats = torrent.tracker_stats.as_deref().unwrap_or_default();
+    let tracker_hosts: Vec<String> = tracker_stats.iter().filter_map(tracker_host).collect();
+    let trackers: Vec<String> = tracker_stats
+        .iter()
+        .map(|stat| stat.announce.clone())
+        .collect();
+
+    for (idx, rule) in CONFIG.rules.rules.iter().enumerate() {
+        if rule.needs_metadata() && !has_metadata {
+            return Decision::AwaitingMetadata;
+        }
+
+        if rule.matches(name, &tracker_hosts, &files, size) {
+            let download_dir = rule.download_dir.clone().or_else(|| {
+                CONFIG
+                    .categories
+                    .map
+                    .get(&rule.category)
+                    .and_then(|category| category.default_dir.clone())
+            });
+            return Decision::Assign(Assignment {
+                category: rule.category.clone(),
+                download_dir,
+                reason: format!("rule #{}", idx + 1),
+            });
+        }
+    }
+
+    match CONFIG.categories.auto_assign(name, &trackers) {
+        Some(category) => Decision::Assign(Assignment {
+            category: category.name.clone(),
+            download_dir: category.default_dir.clone(),
+            reason: format!("regexes of category \"{}\"", category.name),
+        }),
+        None => Decision::NoMatch,
+    }
+}
+
+fn log_assignment(torrent: &Torrent, assignment: &Assignment) {
+    tracing::info!(
+        "Auto-categorization: {} matched \"{}\", ID={:?}, setting category \"{}\"{}",
+        assignment.reason,
+        torrent.name.as_deref().unwrap_or_default(),
+        torrent.id(),
+        assignment.category,
+        assignment
+            .download_dir
+            .as_ref()
+            .map(|dir| format!(" and moving to {dir}"))
+            .unwrap_or_default()
+    );
+}
+
+fn log_no_match(torrent: &Torrent) {
+    tracing::info!(
+        "Auto-categorization: nothing matched \"{}\", ID={:?}",
+        torrent.name.as_deref().unwrap_or_default(),
+        torrent.id()
+    );
+}
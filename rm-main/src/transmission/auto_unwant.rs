@@ -0,0 +1,65 @@
+use rm_config::CONFIG;
+use transmission_rpc::types::{Id, Torrent, TorrentSetArgs};
+
+use crate::tui::ctx::CTX;
+
+use super::{
+    new_torrents::{Handled, NewTorrentHandler},
+    TorrentAction,
+};
+
+/// Sets files matching `unwanted_files` from config.toml to not be downloaded
+/// in torrents that show up after the first update.
+pub fn handler() -> Option<NewTorrentHandler> {
+    if CONFIG.torrents_tab.unwanted_files.is_empty() {
+        return None;
+    }
+
+    Some(NewTorrentHandler {
+        name: "Auto-unwanting",
+        wants: |_| true,
+        handle: unwant,
+    })
+}
+
+fn unwant(torrent: &Torrent) -> Handled {
+    let Some(Id::Id(id)) = torrent.id() else {
+        return Handled::Done;
+    };
+
+    let files = torrent.files.as_deref().unwrap_or_default();
+    if files.is_empty() {
+        return Handled::AwaitingMetadata;
+    }
+
+    let unwanted: Vec<usize> = files
+        .iter()
+        .enumerate()
+        .filter(|(_, file)| {
+            CONFIG
+                .torrents_tab
+                .unwanted_files
+                .iter()
+                .any(|glob| glob.is_match(&file.name))
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if unwanted.is_empty() {
+        return Handled::Done;
+    }
+
+    tracing::info!(
+        "Unwanting {} file(s) of \"{}\", ID={id}",
+        unwanted.len(),
+        torrent.name.as_deref().unwrap_or_default()
+    );
+
+    let args = TorrentSetArgs::default().files_unwanted(unwanted);
+    CTX.send_torrent_action(TorrentAction::SetArgs(
+        Box::new(args),
+        Some(vec![Id::Id(id)]),
+    ));
+
+    Handled::Done
+}
@@ -0,0 +1,181 @@
+use std::fmt::Display;
+
+use rm_config::{categories::Category, CONFIG};
+use transmission_rpc::{
+    types::{Id, Result, TorrentAction as RPCAction, TorrentAddArgs, TorrentAddedOrDuplicate},
+    TransClient,
+};
+
+use super::action::edit_labels;
+
+/// A torrent the daemon has accepted.
+pub struct AddedTorrent {
+    pub id: Option<Id>,
+    /// Why the policy of its category couldn't be applied. The torrent stays
+    /// added regardless.
+    pub policy_error: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+/// Adds a torrent, applying policies of its category. A category given in the
+/// labels is known up front, so the torrent is added paused if it says so.
+///
+/// Torrents added without a category are left to `auto_categorize`.
+pub async fn add_torrent(
+    client: &mut TransClient,
+    mut args: TorrentAddArgs,
+) -> Result<AddedTorrent> {
+    let category = args
+        .labels
+        .as_ref()
+        .and_then(|labels| CONFIG.categories.category_label(labels))
+        .and_then(|label| CONFIG.categories.map.get(label));
+
+    if let Some(category) = category {
+        args.paused = Some(args.paused.unwrap_or(false) || category.start_paused);
+        if args.download_dir.is_none() {
+            args.download_dir = category.default_dir.clone();
+        }
+    }
+
+    let response = client.torrent_add(args).await?;
+    let torrent = match response.arguments {
+        TorrentAddedOrDuplicate::TorrentAdded(torrent) => torrent,
+        TorrentAddedOrDuplicate::TorrentDuplicate(torrent) => {
+            let name = torrent.name.unwrap_or_default();
+            return Err(Box::new(AddTorrentError::Duplicate(name)));
+        }
+        TorrentAddedOrDuplicate::Error => {
+            return Err(Box::new(AddTorrentError::from_result(response.result)));
+        }
+    };
+    let id = torrent.id();
+
+    let policy_error = match (category, &id) {
+        (Some(category), Some(id)) => apply_category_policy(client, vec![id.clone()], category)
+            .await
+            .err(),
+        _ => None,
+    };
+
+    Ok(AddedTorrent { id, policy_error })
+}
+
+/// Makes `category` the category of torrents with given IDs, keeping their
+/// other labels, and applies its policy.
+pub async fn change_category(client: &mut TransClient, ids: Vec<Id>, category: &str) -> Result<()> {
+    edit_labels(client, ids.clone(), |labels| {
+        CONFIG.categories.set_category(labels, category)
+    })
+    .await?;
+
+    match CONFIG.categories.map.get(category) {
+        Some(category) => apply_category_policy(client, ids, category).await,
+        None => Ok(()),
+    }
+}
+
+/// Gives new torrents a category, moving them to `download_dir` if given and
+/// pausing them if the category says so.
+///
+/// This is for torrents categorized only after they were added, so pausing is
+/// best-effort: they have been running since then and may have downloaded a
+/// bit already. Categories known before adding go through `add_torrent`.
+pub async fn assign_category(
+    client: &mut TransClient,
+    ids: Vec<Id>,
+    category: &str,
+    download_dir: Option<String>,
+) -> Result<()> {
+    change_category(client, ids.clone(), category).await?;
+
+    if let Some(download_dir) = download_dir {
+        client
+            .torrent_set_location(ids.clone(), download_dir, Some(true))
+            .await?;
+    }
+
+    if CONFIG
+        .categories
+        .map
+        .get(category)
+        .is_some_and(|category| category.start_paused)
+    {
+        client.torrent_action(RPCAction::Stop, ids).await?;
+    }
+
+    Ok(())
+}
+
+/// Why the daemon refused to add a torrent.
+#[derive(Debug)]
+pub enum AddTorrentError {
+    /// Name of the torrent that's already there.
+    Duplicate(String),
+    InvalidMetainfo,
+    /// The daemon couldn't download the .torrent file, with its reason.
+    UnreachableUrl(String),
+    Other(String),
+}
+
+impl AddTorrentError {
+    fn from_result(result: String) -> Self {
+        if result.contains("invalid or corrupt torrent") || result.contains("unrecognized info") {
+            Self::InvalidMetainfo
+        } else if result.contains("gotMetadataFromURL") || result.contains("Couldn't fetch") {
+            Self::UnreachableUrl(result)
+        } else if result.contains("duplicate torrent") {
+            Self::Duplicate(String::new())
+        } else {
+            Self::Other(result)
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            AddTorrentError::Duplicate(_) => "Torrent already added",
+            AddTorrentError::InvalidMetainfo => "Invalid torrent",
+            AddTorrentError::UnreachableUrl(_) => "Unreachable URL",
+            AddTorrentError::Other(_) => "Failed to add torrent",
+        }
+    }
+}
+
+impl Display for AddTorrentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddTorrentError::Duplicate(name) if name.is_empty() => {
+                write!(f, "the torrent has already been added")
+            }
+            AddTorrentError::Duplicate(name) => {
+                write!(f, "the torrent has already been added as \"{name}\"")
+            }
+            AddTorrentError::InvalidMetainfo => {
+                write!(f, "the daemon couldn't read the torrent's metainfo")
+            }
+            AddTorrentError::UnreachableUrl(reason) => {
+                write!(f, "the daemon couldn't download the torrent: {reason}")
+            }
+            AddTorrentError::Other(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AddTorrentError {}
+
+/// Sets limits and seeding goals of a category to torrents with given IDs.
+pub async fn apply_category_policy(
+    client: &mut TransClient,
+    ids: Vec<Id>,
+    category: &Category,
+) -> Result<()> {
+    if let Some(args) = category.torrent_set_args() {
+        tracing::info!(
+            "Applying policy of category \"{}\" to torrents, IDs={:?}",
+            category.name,
+            ids
+        );
+        client.torrent_set(args, Some(ids)).await?;
+    }
+
+    Ok(())
+}
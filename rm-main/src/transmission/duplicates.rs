@@ -0,0 +1,99 @@
+use base64::Engine;
+use rm_shared::{action::DuplicateTorrent, info_hash, magnet::MagnetLink, metainfo::Metainfo};
+use transmission_rpc::{
+    types::{Id, Result, TorrentAddArgs, TorrentGetField, TrackerList},
+    TransClient,
+};
+
+/// Looks up a torrent that the daemon already has under the info hash of
+/// what `args` would add.
+///
+/// Torrents added from URLs can't be identified before they're downloaded,
+/// so they're never reported as duplicates.
+pub async fn find_duplicate(
+    client: &mut TransClient,
+    args: &TorrentAddArgs,
+) -> Result<Option<DuplicateTorrent>> {
+    let Some((hash, trackers)) = identify(args) else {
+        return Ok(None);
+    };
+
+    let fields = vec![
+        TorrentGetField::Id,
+        TorrentGetField::Name,
+        TorrentGetField::TrackerList,
+    ];
+    let torrents = client
+        .torrent_get(Some(fields), Some(vec![Id::Hash(hash)]))
+        .await?
+        .arguments
+        .torrents;
+
+    let Some(torrent) = torrents.into_iter().next() else {
+        return Ok(None);
+    };
+    let Some(id) = torrent.id else {
+        return Ok(None);
+    };
+
+    let tracker_list = torrent.tracker_list.unwrap_or_default();
+    let mut new_trackers: Vec<String> = vec![];
+    for tracker in trackers {
+        if !tracker_list.lines().any(|known| known == tracker) && !new_trackers.contains(&tracker) {
+            new_trackers.push(tracker);
+        }
+    }
+
+    Ok(Some(DuplicateTorrent {
+        id,
+        name: torrent.name.unwrap_or_default(),
+        tracker_list,
+        new_trackers,
+    }))
+}
+
+/// The duplicate's tracker list with its new trackers appended, each in its own tier.
+pub fn merged_tracker_list(duplicate: &DuplicateTorrent) -> TrackerList {
+    let mut list: Vec<String> = duplicate.tracker_list.lines().map(String::from).collect();
+    for tracker in &duplicate.new_trackers {
+        list.push(String::new());
+        list.push(tracker.clone());
+    }
+    TrackerList(list)
+}
+
+/// Info hash and trackers of a magnet or an uploaded .torrent file.
+fn identify(args: &TorrentAddArgs) -> Option<(String, Vec<String>)> {
+    if let Some(metainfo) = &args.metainfo {
+        let contents = base64::engine::general_purpose::STANDARD
+            .decode(metainfo)
+            .ok()?;
+        let hash = info_hash::from_metainfo(&contents).ok()?;
+        let trackers = Metainfo::from_bytes(&contents)
+            .map(|metainfo| metainfo.trackers)
+            .unwrap_or_default();
+        return Some((hash, trackers));
+    }
+
+    let filename = args.filename.as_deref()?;
+    if !filename.starts_with("magnet:") {
+        return None;
+    }
+
+    let magnet = match MagnetLink::parse(filename) {
+        Ok(magnet) => magnet,
+        Err(err) => {
+            tracing::warn!("Not looking for a duplicate of an invalid magnet link: {err}");
+            return None;
+        }
+    };
+    for tracker in &magnet.skipped_trackers {
+        tracing::warn!("Skipping unusable tracker \"{tracker}\" of a magnet link");
+    }
+    let Some(hash) = magnet.info_hash else {
+        tracing::info!("Not looking for a duplicate of a magnet link without a v1 info hash");
+        return None;
+    };
+
+    Some((hash, magnet.trackers))
+}
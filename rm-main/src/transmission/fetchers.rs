@@ -8,15 +8,18 @@ use rm_shared::action::UpdateAction;
 
 use crate::tui::ctx::CTX;
 
-use super::TorrentAction;
+use super::{stats_history::StatsRecorder, TorrentAction};
 
 pub async fn stats() {
+    let mut recorder = StatsRecorder::open();
+
     loop {
         let (stats_tx, stats_rx) = oneshot::channel();
         CTX.send_torrent_action(TorrentAction::GetSessionStats(stats_tx));
 
         match stats_rx.await.unwrap() {
             Ok(stats) => {
+                recorder.record(&stats);
                 CTX.send_update_action(UpdateAction::SessionStats(stats));
             }
             Err(err_message) => {
@@ -86,6 +89,20 @@ pub async fn torrents() {
             TorrentGetField::ErrorString,
             TorrentGetField::Labels,
             TorrentGetField::FileStats,
+            TorrentGetField::TotalSize,
+            TorrentGetField::DownloadedEver,
+            TorrentGetField::DoneDate,
+            TorrentGetField::TrackerStats,
+            TorrentGetField::HashString,
+            TorrentGetField::QueuePosition,
+            TorrentGetField::IsPrivate,
+            TorrentGetField::Comment,
+            TorrentGetField::Creator,
+            TorrentGetField::SecondsSeeding,
+            TorrentGetField::DesiredAvailable,
+            TorrentGetField::LeftUntilDone,
+            TorrentGetField::PeersSendingToUs,
+            TorrentGetField::PeersGettingFromUs,
         ];
         let (torrents_tx, torrents_rx) = oneshot::channel();
         CTX.send_torrent_action(TorrentAction::GetTorrents(fields, torrents_tx));
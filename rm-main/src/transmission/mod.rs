@@ -1,5 +1,11 @@
 mod action;
+pub mod auto_categorize;
+pub mod auto_unwant;
+pub mod categories;
+pub mod duplicates;
 pub mod fetchers;
+pub mod new_torrents;
+pub mod stats_history;
 pub mod utils;
 
 pub use action::{action_handler, TorrentAction};
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet};
+
+use rm_shared::action::UpdateAction;
+use tokio::sync::{mpsc::UnboundedReceiver, oneshot};
+use transmission_rpc::types::{Id, Torrent};
+
+use crate::tui::ctx::CTX;
+
+use super::TorrentAction;
+
+/// Torrent's ID and labels, as seen in `UpdateAction::UpdateTorrents`.
+pub type TorrentLabels = (Id, Vec<String>);
+
+pub enum Handled {
+    Done,
+    /// The torrent is a magnet without metadata yet, it will be handed over
+    /// again once the metadata shows up.
+    AwaitingMetadata,
+}
+
+/// Something done with every torrent that shows up after the first update.
+pub struct NewTorrentHandler {
+    pub name: &'static str,
+    /// Whether a torrent with these labels should be handled at all.
+    pub wants: fn(&[String]) -> bool,
+    pub handle: fn(&Torrent) -> Handled,
+}
+
+/// Hands torrents added after the first update (from anywhere) over to the
+/// registered handlers. Torrents present on the first update are left alone.
+pub async fn watch(
+    handlers: Vec<NewTorrentHandler>,
+    mut torrents_rx: UnboundedReceiver<Vec<TorrentLabels>>,
+) {
+    let mut seen = HashSet::new();
+    // Handlers (by index) still waiting for a torrent's metadata.
+    let mut awaiting_metadata: HashMap<i64, Vec<usize>> = HashMap::new();
+    let mut first_update = true;
+
+    while let Some(torrents) = torrents_rx.recv().await {
+        let mut pending = HashMap::new();
+
+        for (id, labels) in torrents {
+            let Id::Id(id) = id else {
+                continue;
+            };
+
+            let is_new = seen.insert(id);
+            if first_update {
+                continue;
+            }
+
+            let handler_idxs: Vec<usize> = if is_new {
+                (0..handlers.len()).collect()
+            } else if let Some(idxs) = awaiting_metadata.remove(&id) {
+                idxs
+            } else {
+                continue;
+            };
+
+            let handler_idxs: Vec<usize> = handler_idxs
+                .into_iter()
+                .filter(|&idx| (handlers[idx].wants)(&labels))
+                .collect();
+
+            if !handler_idxs.is_empty() {
+                pending.insert(id, (handler_idxs, is_new));
+            }
+        }
+
+        first_update = false;
+
+        if pending.is_empty() {
+            continue;
+        }
+
+        let ids = pending.keys().map(|&id| Id::Id(id)).collect();
+        let (torrents_tx, torrents_rx) = oneshot::channel();
+        CTX.send_torrent_action(TorrentAction::GetTorrentsById(ids, torrents_tx));
+
+        match torrents_rx.await.unwrap() {
+            Ok(torrents) => {
+                for torrent in torrents {
+                    let Some(Id::Id(id)) = torrent.id() else {
+                        continue;
+                    };
+                    let Some((handler_idxs, is_new)) = pending.remove(&id) else {
+                        continue;
+                    };
+
+                    let still_awaiting: Vec<usize> = handler_idxs
+                        .into_iter()
+                        .filter(|&idx| {
+                            matches!((handlers[idx].handle)(&torrent), Handled::AwaitingMetadata)
+                        })
+                        .collect();
+
+                    if still_awaiting.is_empty() {
+                        continue;
+                    }
+
+                    if is_new {
+                        for &idx in &still_awaiting {
+                            tracing::info!(
+                                "{}: waiting for metadata of \"{}\", ID={id}",
+                                handlers[idx].name,
+                                torrent.name.as_deref().unwrap_or_default()
+                            );
+                        }
+                    }
+                    awaiting_metadata.insert(id, still_awaiting);
+                }
+            }
+            Err(err_message) => {
+                CTX.send_update_action(UpdateAction::Error(err_message));
+            }
+        }
+    }
+}
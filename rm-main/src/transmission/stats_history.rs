@@ -0,0 +1,187 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use chrono::Utc;
+use transmission_rpc::types::SessionStats;
+
+/// Samples are aggregated per this many seconds before being written.
+pub const SAMPLE_INTERVAL: i64 = 60;
+/// Samples older than this are dropped from the file on startup.
+pub const MAX_AGE: i64 = 30 * 24 * 60 * 60;
+
+const SAMPLE_LEN: usize = 40;
+
+/// Sample of the interval being recorded, shared so that it can be shown
+/// before it's written and written on quit.
+static IN_PROGRESS: Mutex<Option<StatsSample>> = Mutex::new(None);
+
+/// Traffic of a single interval, stored as fixed-size little-endian records.
+#[derive(Clone, Copy, Default)]
+pub struct StatsSample {
+    /// Start of the interval as a UNIX timestamp.
+    pub timestamp: i64,
+    pub downloaded: u64,
+    pub uploaded: u64,
+    pub peak_download_speed: u64,
+    pub peak_upload_speed: u64,
+}
+
+impl StatsSample {
+    fn to_bytes(self) -> [u8; SAMPLE_LEN] {
+        let mut bytes = [0; SAMPLE_LEN];
+        bytes[0..8].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.downloaded.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.uploaded.to_le_bytes());
+        bytes[24..32].copy_from_slice(&self.peak_download_speed.to_le_bytes());
+        bytes[32..40].copy_from_slice(&self.peak_upload_speed.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let field = |idx: usize| -> [u8; 8] { bytes[idx * 8..(idx + 1) * 8].try_into().unwrap() };
+
+        Self {
+            timestamp: i64::from_le_bytes(field(0)),
+            downloaded: u64::from_le_bytes(field(1)),
+            uploaded: u64::from_le_bytes(field(2)),
+            peak_download_speed: u64::from_le_bytes(field(3)),
+            peak_upload_speed: u64::from_le_bytes(field(4)),
+        }
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    let xdg = xdg::BaseDirectories::with_prefix("rustmission").ok()?;
+    xdg.place_data_file("stats.bin").ok()
+}
+
+fn read_samples() -> io::Result<Vec<StatsSample>> {
+    let Some(path) = history_path() else {
+        return Ok(vec![]);
+    };
+
+    match fs::read(path) {
+        Ok(bytes) => Ok(bytes
+            .chunks_exact(SAMPLE_LEN)
+            .map(StatsSample::from_bytes)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Loads samples recorded since the given UNIX timestamp.
+pub fn load(since: i64) -> io::Result<Vec<StatsSample>> {
+    let mut samples = read_samples()?;
+    samples.retain(|sample| sample.timestamp >= since);
+    Ok(samples)
+}
+
+/// Sample of the interval that hasn't been written yet.
+pub fn in_progress() -> Option<StatsSample> {
+    *IN_PROGRESS.lock().unwrap()
+}
+
+/// Writes the sample of the current interval, so that it isn't lost on quit.
+pub fn flush() {
+    if let Some(sample) = IN_PROGRESS.lock().unwrap().take() {
+        save(sample);
+    }
+}
+
+/// Aggregates `SessionStats` into samples and appends them to the history file.
+pub struct StatsRecorder {
+    last_cumulative: Option<(i64, i64)>,
+}
+
+impl StatsRecorder {
+    /// Also drops samples older than `MAX_AGE` from the history file.
+    pub fn open() -> Self {
+        if let Err(e) = prune() {
+            tracing::error!("Failed to prune transfer statistics history: {e}");
+        }
+
+        Self {
+            last_cumulative: None,
+        }
+    }
+
+    pub fn record(&mut self, stats: &SessionStats) {
+        let now = Utc::now().timestamp();
+        let interval_start = now - now.rem_euclid(SAMPLE_INTERVAL);
+
+        let mut in_progress = IN_PROGRESS.lock().unwrap();
+        if let Some(sample) = in_progress.take_if(|sample| sample.timestamp != interval_start) {
+            save(sample);
+        }
+
+        let cumulative = (
+            stats.cumulative_stats.downloaded_bytes,
+            stats.cumulative_stats.uploaded_bytes,
+        );
+        // Counters can go back if the daemon's stats get reset.
+        let (downloaded, uploaded) = match self.last_cumulative {
+            Some((last_down, last_up)) => (
+                u64::try_from(cumulative.0 - last_down).unwrap_or(0),
+                u64::try_from(cumulative.1 - last_up).unwrap_or(0),
+            ),
+            None => (0, 0),
+        };
+        self.last_cumulative = Some(cumulative);
+
+        let current = in_progress.get_or_insert(StatsSample {
+            timestamp: interval_start,
+            ..Default::default()
+        });
+        current.downloaded += downloaded;
+        current.uploaded += uploaded;
+        current.peak_download_speed = current
+            .peak_download_speed
+            .max(u64::try_from(stats.download_speed).unwrap_or(0));
+        current.peak_upload_speed = current
+            .peak_upload_speed
+            .max(u64::try_from(stats.upload_speed).unwrap_or(0));
+    }
+}
+
+fn save(sample: StatsSample) {
+    if let Err(e) = append(sample) {
+        tracing::error!("Failed to save transfer statistics: {e}");
+    }
+}
+
+fn append(sample: StatsSample) -> io::Result<()> {
+    let Some(path) = history_path() else {
+        return Ok(());
+    };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&sample.to_bytes())
+}
+
+fn prune() -> io::Result<()> {
+    let samples = read_samples()?;
+    let oldest_allowed = Utc::now().timestamp() - MAX_AGE;
+
+    if samples
+        .iter()
+        .all(|sample| sample.timestamp >= oldest_allowed)
+    {
+        return Ok(());
+    }
+
+    let Some(path) = history_path() else {
+        return Ok(());
+    };
+
+    let bytes: Vec<u8> = samples
+        .into_iter()
+        .filter(|sample| sample.timestamp >= oldest_allowed)
+        .flat_map(StatsSample::to_bytes)
+        .collect();
+    fs::write(path, bytes)
+}
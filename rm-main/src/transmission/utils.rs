@@ -1,5 +1,8 @@
 use rm_config::CONFIG;
-use transmission_rpc::{types::BasicAuth, TransClient};
+use transmission_rpc::{
+    types::{BasicAuth, TrackerStat},
+    TransClient,
+};
 
 pub fn new_client() -> TransClient {
     let user = CONFIG
@@ -19,3 +22,11 @@ pub fn new_client() -> TransClient {
 
     TransClient::with_auth(CONFIG.connection.url.clone(), auth)
 }
+
+/// Host of the tracker's announce URL.
+pub fn tracker_host(tracker_stat: &TrackerStat) -> Option<String> {
+    match url::Url::parse(&tracker_stat.announce) {
+        Ok(url) => url.host_str().map(str::to_string),
+        Err(_) => Some(tracker_stat.host.clone()),
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     io::stdout,
     panic::{set_hook, take_hook},
+    time::{Duration, Instant},
 };
 
 use crate::{
@@ -13,6 +14,7 @@ use rm_config::CONFIG;
 use rm_shared::{
     action::{Action, UpdateAction},
     current_window::{TorrentWindow, Window},
+    magnet::Link,
 };
 
 use color_eyre::{
@@ -21,7 +23,10 @@ use color_eyre::{
 };
 use crossterm::{
     cursor::Show,
-    event::{DisableMouseCapture, KeyCode, KeyModifiers},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, KeyCode, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, LeaveAlternateScreen},
 };
@@ -33,6 +38,8 @@ use super::{
     tabs::torrents::SESSION_GET,
 };
 
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 pub struct AppKeyEvent(crossterm::event::KeyEvent);
 
 impl From<crossterm::event::KeyEvent> for AppKeyEvent {
@@ -68,7 +75,11 @@ impl AppKeyEvent {
     fn to_action(&self, current_window: Window) -> Option<Action> {
         let keymap = match current_window {
             Window::Torrents(torrents_tab_current_window) => match torrents_tab_current_window {
-                TorrentWindow::General => &CONFIG.keybindings.torrents_tab.map,
+                TorrentWindow::General
+                | TorrentWindow::Inspector
+                | TorrentWindow::Statistics
+                | TorrentWindow::TorrentPicker
+                | TorrentWindow::ColumnEditor => &CONFIG.keybindings.torrents_tab.map,
                 TorrentWindow::FileViewer => &CONFIG.keybindings.torrents_tab_file_viewer.map,
             },
             Window::Search(_) => &CONFIG.keybindings.search_tab.map,
@@ -91,6 +102,8 @@ pub struct App {
     update_rx: UnboundedReceiver<UpdateAction>,
     main_window: MainWindow,
     mode: Mode,
+    /// When and where the left button was last clicked, to tell double clicks apart.
+    last_click: Option<(Instant, u16, u16)>,
 }
 
 impl App {
@@ -129,6 +142,7 @@ impl App {
             action_rx,
             update_rx,
             mode: Mode::Normal,
+            last_click: None,
         })
     }
 
@@ -139,17 +153,31 @@ impl App {
 
         set_hook(Box::new(move |panic_info| {
             let _ = disable_raw_mode();
-            let _ = execute!(stdout(), LeaveAlternateScreen, Show, DisableMouseCapture);
+            let _ = execute!(
+                stdout(),
+                LeaveAlternateScreen,
+                Show,
+                DisableMouseCapture,
+                DisableBracketedPaste
+            );
             original_hook(panic_info);
         }));
 
         terminal.init()?;
+        // Without it a paste arrives as a stream of key presses that can trigger keybindings.
+        execute!(stdout(), EnableBracketedPaste)?;
+        if !CONFIG.general.mouse {
+            execute!(stdout(), DisableMouseCapture)?;
+        }
 
         self.render(&mut terminal)?;
 
-        self.main_loop(&mut terminal).await?;
+        let res = self.main_loop(&mut terminal).await;
+        transmission::stats_history::flush();
 
+        execute!(stdout(), DisableBracketedPaste)?;
         terminal.exit()?;
+        res?;
         Ok(())
     }
 
@@ -169,7 +197,7 @@ impl App {
                 event = tui_event => {
                     let event = event.unwrap();
 
-                    use crossterm::event::{Event, MouseEventKind};
+                    use crossterm::event::Event;
                     match event {
                         Event::Key(key_event) => {
                             let app_key_event = AppKeyEvent::from(key_event);
@@ -181,11 +209,14 @@ impl App {
                                 self.handle_user_action(action);
                             }
                         },
-                        Event::Mouse(mouse_event) => match mouse_event.kind {
-                            MouseEventKind::ScrollDown => self.handle_user_action(Action::ScrollDownBy(3)),
-                            MouseEventKind::ScrollUp => self.handle_user_action(Action::ScrollUpBy(3)),
-                            _ => (),
-                        },
+                        Event::Mouse(mouse_event) if CONFIG.general.mouse => self.handle_mouse(mouse_event),
+                        Event::Paste(text) => {
+                            if self.mode == Mode::Input {
+                                self.handle_user_action(Action::Paste(text));
+                            } else if is_link(&text) {
+                                self.handle_user_action(Action::PasteLink(text.trim().to_string()));
+                            }
+                        }
                         Event::Resize(_, _) => self.render(terminal).unwrap(),
                         _ => (),
                     }
@@ -234,6 +265,31 @@ impl App {
         }
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::ScrollDown => self.handle_user_action(Action::ScrollDownBy(3)),
+            MouseEventKind::ScrollUp => self.handle_user_action(Action::ScrollUpBy(3)),
+            // Clicks would change what an open prompt is about.
+            MouseEventKind::Down(MouseButton::Left) if self.mode == Mode::Normal => {
+                let (column, row) = (event.column, event.row);
+                let now = Instant::now();
+                let is_double_click = self.last_click.is_some_and(|(at, last_column, last_row)| {
+                    (last_column, last_row) == (column, row)
+                        && now.duration_since(at) < DOUBLE_CLICK_INTERVAL
+                });
+
+                if is_double_click {
+                    self.last_click = None;
+                    self.handle_user_action(Action::DoubleClick(column, row));
+                } else {
+                    self.last_click = Some((now, column, row));
+                    self.handle_user_action(Action::Click(column, row));
+                }
+            }
+            _ => (),
+        }
+    }
+
     async fn handle_update_action(&mut self, action: UpdateAction) -> Result<()> {
         match action {
             UpdateAction::UnrecoverableError(report) => return Err(*report),
@@ -255,6 +311,11 @@ impl App {
     }
 }
 
+/// Whether pasted text is something that can be added as a torrent.
+fn is_link(text: &str) -> bool {
+    Link::parse(text).is_ok()
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Mode {
     Input,
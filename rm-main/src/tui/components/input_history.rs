@@ -0,0 +1,92 @@
+use std::{fs, io, path::PathBuf};
+
+const MAX_ENTRIES: usize = 100;
+
+/// Previously submitted values of a prompt, kept in the XDG data dir at
+/// `rustmission/history/<name>`, one per line, newest last.
+pub struct InputHistory {
+    name: &'static str,
+    entries: Vec<String>,
+    /// Entry being shown, `None` if the user is editing their own text.
+    position: Option<usize>,
+    /// What the user typed before browsing the history.
+    draft: String,
+}
+
+impl InputHistory {
+    pub fn load(name: &'static str) -> Self {
+        let entries = match history_path(name).map(fs::read_to_string) {
+            Some(Ok(history)) => history.lines().map(str::to_string).collect(),
+            Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => {
+                tracing::error!("Failed to read {name} history: {e}");
+                vec![]
+            }
+            _ => vec![],
+        };
+
+        Self {
+            name,
+            entries,
+            position: None,
+            draft: String::new(),
+        }
+    }
+
+    pub fn prev(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            Some(0) => return None,
+            Some(position) => position - 1,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+        };
+
+        self.position = Some(position);
+        Some(&self.entries[position])
+    }
+
+    pub fn next(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+
+        if position < self.entries.len() {
+            self.position = Some(position);
+            Some(&self.entries[position])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stops browsing, e.g. after the shown entry got edited.
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    pub fn push(&mut self, entry: String) {
+        self.position = None;
+
+        if entry.trim().is_empty() {
+            return;
+        }
+
+        self.entries.retain(|existing| *existing != entry);
+        self.entries.push(entry);
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+
+        let Some(path) = history_path(self.name) else {
+            return;
+        };
+        let mut contents = self.entries.join("\n");
+        contents.push('\n');
+        if let Err(e) = fs::write(path, contents) {
+            tracing::error!("Failed to save {} history: {e}", self.name);
+        }
+    }
+}
+
+fn history_path(name: &str) -> Option<PathBuf> {
+    let xdg = xdg::BaseDirectories::with_prefix("rustmission").ok()?;
+    xdg.place_data_file(format!("history/{name}")).ok()
+}
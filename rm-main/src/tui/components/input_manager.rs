@@ -1,17 +1,30 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyEvent};
 use ratatui::{
     prelude::*,
-    widgets::{Clear, Paragraph},
+    widgets::{Clear, List, ListItem, Paragraph},
 };
-use rm_config::CONFIG;
-use tui_input::{backend::crossterm::to_input_request, Input, InputResponse, StateChanged};
+use rm_config::{keymap::InputAction, CONFIG};
+use tui_input::{
+    backend::crossterm::to_input_request, Input, InputRequest, InputResponse, StateChanged,
+};
+
+use crate::tui::components::{input_history::InputHistory, Component};
 
-use crate::tui::components::Component;
+const MAX_COMPLETIONS_SHOWN: usize = 8;
 
 pub struct InputManager {
     input: Input,
     prompt: String,
     autocompletions: Vec<String>,
+    completion_menu: Option<CompletionMenu>,
+    history: Option<Box<InputHistory>>,
+}
+
+/// Candidates being cycled through with Tab, shown in a dropdown.
+struct CompletionMenu {
+    candidates: Vec<String>,
+    /// None until the first cycle, the text is only their common prefix then.
+    selected: Option<usize>,
 }
 
 impl InputManager {
@@ -20,14 +33,15 @@ impl InputManager {
             prompt,
             input: Input::default(),
             autocompletions: vec![],
+            completion_menu: None,
+            history: None,
         }
     }
 
     pub fn new_with_value(prompt: String, value: String) -> Self {
         Self {
-            prompt,
             input: Input::default().with_value(value),
-            autocompletions: vec![],
+            ..Self::new(prompt)
         }
     }
 
@@ -36,14 +50,27 @@ impl InputManager {
         self
     }
 
+    /// Enables Up/Down history of this prompt, shared by all prompts with the same name.
+    pub fn history(mut self, name: &'static str) -> Self {
+        self.history = Some(Box::new(InputHistory::load(name)));
+        self
+    }
+
+    pub fn set_autocompletions(&mut self, autocompletions: Vec<String>) {
+        self.autocompletions = autocompletions;
+        self.completion_menu = None;
+    }
+
     pub fn get_autocompletion(&self) -> Option<&str> {
-        let mut autocompletion = None;
-        for possible_autocompletion in &self.autocompletions {
-            if possible_autocompletion.starts_with(&self.input.to_string()) {
-                autocompletion = Some(possible_autocompletion);
-            }
+        if self.completion_menu.is_some() {
+            return None;
         }
-        autocompletion.map(|x| x.as_str())
+
+        let text = self.input.value();
+        self.autocompletions
+            .iter()
+            .find(|completion| completion.starts_with(text) && *completion != text)
+            .map(String::as_str)
     }
 
     pub fn apply_autocompletion(&mut self) {
@@ -61,32 +88,137 @@ impl InputManager {
         self.input.to_string()
     }
 
+    /// Whether the completion dropdown is open.
+    pub fn is_completing(&self) -> bool {
+        self.completion_menu.is_some()
+    }
+
+    /// Saves the current text in the prompt's history, call it when submitting.
+    pub fn save_history(&mut self) {
+        let text = self.text();
+        if let Some(history) = &mut self.history {
+            history.push(text);
+        }
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> InputResponse {
-        if key.code == KeyCode::Tab {
-            self.apply_autocompletion();
-            return Some(StateChanged {
-                value: true,
-                cursor: true,
-            });
+        let changed = Some(StateChanged {
+            value: true,
+            cursor: true,
+        });
+
+        let action = input_action(&key);
+
+        let request = match action {
+            // Only the torrents filter selects anything.
+            Some(InputAction::SelectAllMatching) => return None,
+            Some(InputAction::NextCompletion) => {
+                self.cycle_completion(true);
+                return changed;
+            }
+            Some(InputAction::PrevCompletion) => {
+                self.cycle_completion(false);
+                return changed;
+            }
+            Some(InputAction::HistoryPrev) => {
+                if self.completion_menu.is_some() {
+                    self.cycle_completion(false);
+                    return changed;
+                }
+                let text = self.text();
+                let entry = self.history.as_mut()?.prev(&text)?.to_string();
+                self.input = self.input.clone().with_value(entry);
+                return changed;
+            }
+            Some(InputAction::HistoryNext) => {
+                if self.completion_menu.is_some() {
+                    self.cycle_completion(true);
+                    return changed;
+                }
+                let entry = self.history.as_mut()?.next()?.to_string();
+                self.input = self.input.clone().with_value(entry);
+                return changed;
+            }
+            Some(InputAction::GoToNextChar) | None
+                if self.visual_cursor() == self.text().chars().count()
+                    && (action.is_some() || key.code == KeyCode::Right)
+                    && self.get_autocompletion().is_some() =>
+            {
+                self.apply_autocompletion();
+                return changed;
+            }
+            Some(action) => Some(input_request(action)),
+            None => to_input_request(&Event::Key(key)),
+        };
+
+        let response = self.input.handle(request?);
+        if response.is_some_and(|response| response.value) {
+            self.completion_menu = None;
+            if let Some(history) = &mut self.history {
+                history.reset();
+            }
         }
+        response
+    }
 
-        if (self.visual_cursor() == self.text().len())
-            && ((key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('f'))
-                || key.code == KeyCode::Right)
-        {
-            self.apply_autocompletion();
-            return Some(StateChanged {
-                value: true,
-                cursor: true,
-            });
+    /// Inserts pasted text at the cursor, dropping line breaks and other control characters.
+    pub fn paste(&mut self, text: &str) -> InputResponse {
+        let mut response = None;
+        for c in text.chars().filter(|c| !c.is_control()) {
+            response = self.input.handle(InputRequest::InsertChar(c)).or(response);
         }
 
-        let event = Event::Key(key);
+        if response.is_some() {
+            self.completion_menu = None;
+            if let Some(history) = &mut self.history {
+                history.reset();
+            }
+        }
+        response
+    }
 
-        if let Some(req) = to_input_request(&event) {
-            self.input.handle(req)
-        } else {
-            None
+    fn cycle_completion(&mut self, forward: bool) {
+        if let Some(menu) = &mut self.completion_menu {
+            let len = menu.candidates.len();
+            let selected = match (menu.selected, forward) {
+                (None, true) => 0,
+                (None, false) => len - 1,
+                (Some(selected), true) => (selected + 1) % len,
+                (Some(selected), false) => (selected + len - 1) % len,
+            };
+            menu.selected = Some(selected);
+            let candidate = menu.candidates[selected].clone();
+            self.input = self.input.clone().with_value(candidate);
+            return;
+        }
+
+        let text = self.text();
+        let candidates: Vec<String> = self
+            .autocompletions
+            .iter()
+            .filter(|completion| completion.starts_with(&text) && **completion != text)
+            .cloned()
+            .collect();
+
+        match candidates.len() {
+            0 => (),
+            1 => self.set_text(candidates[0].clone()),
+            len => {
+                // Extend to what all candidates share first, then cycle.
+                let prefix = common_prefix(&candidates);
+                let selected = if prefix.len() > text.len() {
+                    self.input = self.input.clone().with_value(prefix);
+                    None
+                } else {
+                    let selected = if forward { 0 } else { len - 1 };
+                    self.input = self.input.clone().with_value(candidates[selected].clone());
+                    Some(selected)
+                };
+                self.completion_menu = Some(CompletionMenu {
+                    candidates,
+                    selected,
+                });
+            }
         }
     }
 
@@ -96,6 +228,88 @@ impl InputManager {
 
     pub fn set_text(&mut self, new_text: impl Into<String>) {
         self.input = self.input.clone().with_value(new_text.into());
+        self.completion_menu = None;
+    }
+
+    fn render_completion_menu(&self, f: &mut Frame, rect: Rect) {
+        let Some(menu) = &self.completion_menu else {
+            return;
+        };
+
+        let shown = menu.candidates.len().min(MAX_COMPLETIONS_SHOWN);
+        let offset = (menu.selected.unwrap_or_default() + 1).saturating_sub(shown);
+        let height = u16::try_from(shown).unwrap().min(rect.y);
+        let width = menu
+            .candidates
+            .iter()
+            .map(|candidate| candidate.chars().count())
+            .max()
+            .unwrap_or_default();
+        let x = rect.x + u16::try_from(self.prompt.chars().count()).unwrap_or_default();
+
+        // Shown right above the prompt, over whatever is there.
+        let menu_rect = Rect {
+            x,
+            y: rect.y - height,
+            width: (u16::try_from(width).unwrap_or(u16::MAX).saturating_add(2))
+                .min(rect.right().saturating_sub(x)),
+            height,
+        };
+
+        let items: Vec<ListItem> = menu
+            .candidates
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(shown)
+            .map(|(idx, candidate)| {
+                let item = ListItem::new(format!(" {candidate} "));
+                if Some(idx) == menu.selected {
+                    item.style(Style::default().fg(CONFIG.theme.accent).bold())
+                } else {
+                    item
+                }
+            })
+            .collect();
+
+        f.render_widget(Clear, menu_rect);
+        f.render_widget(List::new(items).bg(CONFIG.theme.highlight_bg), menu_rect);
+    }
+}
+
+/// What an `[input]` keybinding does with the key, BackTab always goes to the previous completion.
+pub fn input_action(key: &KeyEvent) -> Option<InputAction> {
+    if key.code == KeyCode::BackTab {
+        return Some(InputAction::PrevCompletion);
+    }
+
+    CONFIG
+        .keybindings
+        .input
+        .map
+        .get(&(key.code, key.modifiers))
+        .copied()
+}
+
+fn input_request(action: InputAction) -> InputRequest {
+    match action {
+        InputAction::GoToPrevChar => InputRequest::GoToPrevChar,
+        InputAction::GoToNextChar => InputRequest::GoToNextChar,
+        InputAction::GoToPrevWord => InputRequest::GoToPrevWord,
+        InputAction::GoToNextWord => InputRequest::GoToNextWord,
+        InputAction::GoToStart => InputRequest::GoToStart,
+        InputAction::GoToEnd => InputRequest::GoToEnd,
+        InputAction::DeletePrevChar => InputRequest::DeletePrevChar,
+        InputAction::DeleteNextChar => InputRequest::DeleteNextChar,
+        InputAction::DeletePrevWord => InputRequest::DeletePrevWord,
+        InputAction::DeleteNextWord => InputRequest::DeleteNextWord,
+        InputAction::DeleteLine => InputRequest::DeleteLine,
+        InputAction::DeleteTillEnd => InputRequest::DeleteTillEnd,
+        InputAction::HistoryPrev
+        | InputAction::HistoryNext
+        | InputAction::NextCompletion
+        | InputAction::PrevCompletion
+        | InputAction::SelectAllMatching => unreachable!("handled by InputManager"),
     }
 }
 
@@ -107,9 +321,9 @@ impl Component for InputManager {
         let spans = vec![
             Span::styled(
                 self.prompt.as_str(),
-                Style::default().fg(CONFIG.general.accent_color),
+                Style::default().fg(CONFIG.theme.accent),
             ),
-            Span::styled(self.text(), Style::default().fg(Color::White)),
+            Span::styled(self.text(), Style::default().fg(CONFIG.theme.text)),
         ];
 
         let paragraph = Paragraph::new(Line::from(spans));
@@ -119,7 +333,7 @@ impl Component for InputManager {
             u16::try_from(self.prompt.len() + self.text().len() - input.len()).unwrap();
         if let Some(completion) = self.get_autocompletion() {
             let already_typed = u16::try_from(input.chars().count()).unwrap();
-            let span = Span::from(&completion[already_typed as usize..]).dark_gray();
+            let span = Span::from(&completion[input.len()..]).fg(CONFIG.theme.dim);
             let completion_rect = rect.inner(Margin {
                 horizontal: prefix_len + already_typed,
                 vertical: 0,
@@ -133,5 +347,26 @@ impl Component for InputManager {
             y: rect.y,
         };
         f.set_cursor_position(cursor_position);
+
+        self.render_completion_menu(f, rect);
     }
 }
+
+fn common_prefix(strings: &[String]) -> String {
+    let Some((first, rest)) = strings.split_first() else {
+        return String::new();
+    };
+
+    let mut len = first.len();
+    for string in rest {
+        len = first
+            .char_indices()
+            .zip(string.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((idx, c), _)| idx + c.len_utf8())
+            .min(len);
+    }
+
+    first[..len].to_string()
+}
@@ -1,5 +1,5 @@
 use ratatui::{
-    layout::{Margin, Rect},
+    layout::{Constraint, Flex, Layout, Margin, Position, Rect},
     style::{Style, Styled, Stylize},
     text::Line,
     widgets::{block::Title, Block, BorderType},
@@ -9,17 +9,38 @@ use rm_config::CONFIG;
 use crate::tui::main_window::centered_rect;
 
 pub fn popup_close_button_highlight() -> Line<'static> {
-    Line::from(" [ CLOSE ] ".fg(CONFIG.general.accent_color).bold()).right_aligned()
+    Line::from(" [ CLOSE ] ".fg(CONFIG.theme.accent).bold()).right_aligned()
 }
 
 pub fn popup_close_button() -> Line<'static> {
     Line::from(" [CLOSE] ".bold()).right_aligned()
 }
 
+/// Where the close button is drawn on the top or the bottom border of a block
+/// rendered at `block_rect`. Covers its wider, highlighted variant.
+pub fn popup_close_button_rect(block_rect: Rect, on_top: bool) -> Rect {
+    let width = u16::try_from(popup_close_button_highlight().width())
+        .unwrap_or_default()
+        .min(block_rect.width.saturating_sub(2));
+    let y = if on_top {
+        block_rect.y
+    } else {
+        block_rect.bottom().saturating_sub(1)
+    };
+
+    Rect {
+        x: block_rect.right().saturating_sub(width + 1),
+        y,
+        width,
+        height: 1,
+    }
+}
+
 pub fn popup_block(title: &str) -> Block {
-    let title_style = Style::default().fg(CONFIG.general.accent_color);
+    let title_style = Style::default().fg(CONFIG.theme.accent);
     Block::bordered()
         .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(CONFIG.theme.border))
         .title(Title::from(title.set_style(title_style)))
 }
 
@@ -37,5 +58,34 @@ pub fn popup_rects(rect: Rect, percent_x: u16, percent_y: u16) -> (Rect, Rect, R
 pub fn keybinding_style() -> Style {
     Style::default()
         .underlined()
-        .underline_color(CONFIG.general.accent_color)
+        .underline_color(CONFIG.theme.accent)
+}
+
+/// Where each of `titles` lands when a `Tabs` widget with a column of padding
+/// on both sides of a title and a one column wide divider is rendered into
+/// `area`. Clicks on a tab are hit-tested against these.
+pub fn tab_rects<'a>(area: Rect, titles: impl IntoIterator<Item = &'a str>) -> Vec<Rect> {
+    let mut x = area.x;
+    titles
+        .into_iter()
+        .map(|title| {
+            let width = u16::try_from(Line::raw(title).width())
+                .unwrap_or(u16::MAX)
+                .saturating_add(2);
+            let rect = Rect::new(x, area.y, width, 1).intersection(area);
+            x = x.saturating_add(width).saturating_add(1);
+            rect
+        })
+        .collect()
+}
+
+/// Index of the column under `column` in [`ratatui::widgets::Table`] with these
+/// widths, rendered at `rect` with its default spacing.
+pub fn table_column_at(widths: &[Constraint], rect: Rect, column: u16) -> Option<usize> {
+    Layout::horizontal(widths)
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(rect)
+        .iter()
+        .position(|area| area.contains(Position::new(column, area.y)))
 }
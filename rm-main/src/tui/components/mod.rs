@@ -1,11 +1,12 @@
+mod input_history;
 mod input_manager;
 mod misc;
 mod table;
 
-pub use input_manager::InputManager;
+pub use input_manager::{input_action, InputManager};
 pub use misc::{
     keybinding_style, popup_block, popup_block_with_close_highlight, popup_close_button,
-    popup_close_button_highlight, popup_rects,
+    popup_close_button_highlight, popup_close_button_rect, popup_rects, tab_rects, table_column_at,
 };
 pub use table::GenericTable;
 
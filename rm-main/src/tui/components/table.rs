@@ -1,6 +1,6 @@
 use std::cell::RefCell;
 
-use ratatui::widgets::TableState;
+use ratatui::{layout::Rect, widgets::TableState};
 
 pub struct GenericTable<T: Clone> {
     pub state: RefCell<TableState>,
@@ -100,16 +100,28 @@ impl<T: Clone> GenericTable<T> {
         }
     }
 
+    /// Index of the item on the given terminal row of a table with one line per
+    /// item, rendered at `rect`.
+    pub fn item_at(&self, rect: Rect, has_header: bool, row: u16) -> Option<usize> {
+        let first_row = rect.y + u16::from(has_header);
+        if row < first_row || row >= rect.bottom() {
+            return None;
+        }
+
+        let idx = self.state.borrow().offset() + usize::from(row - first_row);
+        (idx < self.get_len()).then_some(idx)
+    }
+
     pub fn select_first(&mut self) {
         self.state.borrow_mut().select_first();
     }
 
     pub fn select_last(&mut self) {
-        if self.items.is_empty() {
+        if self.get_len() == 0 {
             return;
         }
 
         let mut state = self.state.borrow_mut();
-        state.select(Some(self.items.len() - 1));
+        state.select(Some(self.get_len() - 1));
     }
 }
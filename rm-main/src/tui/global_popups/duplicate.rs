@@ -0,0 +1,147 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Paragraph, Wrap},
+};
+use rm_config::CONFIG;
+use rm_shared::action::{Action, DuplicateTorrent};
+use transmission_rpc::types::{Id, TorrentSetArgs};
+
+use crate::{
+    transmission::{duplicates, TorrentAction},
+    tui::{
+        components::{popup_block, popup_rects, Component, ComponentAction},
+        ctx::CTX,
+    },
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Jump,
+    MergeTrackers,
+    Cancel,
+}
+
+/// Tells that a torrent being added is already there, offering to jump to it
+/// or to give it the trackers it lacks.
+pub struct DuplicatePopup {
+    duplicate: DuplicateTorrent,
+    choices: Vec<Choice>,
+    selected: usize,
+    choices_rect: Rect,
+}
+
+impl DuplicatePopup {
+    pub fn new(duplicate: DuplicateTorrent) -> Self {
+        let mut choices = vec![Choice::Jump];
+        if !duplicate.new_trackers.is_empty() {
+            choices.push(Choice::MergeTrackers);
+        }
+        choices.push(Choice::Cancel);
+
+        Self {
+            duplicate,
+            choices,
+            selected: 0,
+            choices_rect: Rect::default(),
+        }
+    }
+
+    fn choice_label(&self, choice: Choice) -> String {
+        match choice {
+            Choice::Jump => "Jump to it".to_string(),
+            Choice::MergeTrackers => match self.duplicate.new_trackers.len() {
+                1 => "Merge 1 new tracker".to_string(),
+                len => format!("Merge {len} new trackers"),
+            },
+            Choice::Cancel => "Cancel".to_string(),
+        }
+    }
+
+    fn confirm(&self) {
+        match self.choices[self.selected] {
+            Choice::Jump => CTX.send_action(Action::JumpToTorrent(self.duplicate.id)),
+            Choice::MergeTrackers => {
+                let args = TorrentSetArgs::default()
+                    .tracker_list(duplicates::merged_tracker_list(&self.duplicate));
+                CTX.send_torrent_action(TorrentAction::SetArgs(
+                    Box::new(args),
+                    Some(vec![Id::Id(self.duplicate.id)]),
+                ));
+            }
+            Choice::Cancel => (),
+        }
+    }
+}
+
+impl Component for DuplicatePopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        match action {
+            _ if action.is_soft_quit() => return ComponentAction::Quit,
+            A::Confirm => {
+                self.confirm();
+                return ComponentAction::Quit;
+            }
+            A::Click(column, row) | A::DoubleClick(column, row)
+                if self.choices_rect.contains(Position::new(column, row)) =>
+            {
+                let idx = usize::from(row - self.choices_rect.y);
+                if idx < self.choices.len() {
+                    self.selected = idx;
+                    self.confirm();
+                    return ComponentAction::Quit;
+                }
+                return ComponentAction::Nothing;
+            }
+            A::Up | A::Left => self.selected = self.selected.saturating_sub(1),
+            A::Down | A::Right => {
+                self.selected = (self.selected + 1).min(self.choices.len() - 1);
+            }
+            _ => return ComponentAction::Nothing,
+        }
+
+        CTX.send_action(A::Render);
+        ComponentAction::Nothing
+    }
+
+    fn render(&mut self, f: &mut Frame, _rect: Rect) {
+        let (popup_rect, block_rect, text_rect) = popup_rects(f.area(), 50, 40);
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+
+        let message = vec![
+            Line::from("This torrent has already been added as"),
+            Line::from(self.duplicate.name.as_str()).style(highlight_style),
+        ];
+
+        let choices_height = u16::try_from(self.choices.len()).unwrap_or(u16::MAX);
+        let [message_rect, choices_rect] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(choices_height)])
+                .areas(text_rect);
+        self.choices_rect = choices_rect;
+
+        let choices: Vec<Line> = self
+            .choices
+            .iter()
+            .enumerate()
+            .map(|(i, choice)| {
+                let label = self.choice_label(*choice);
+                if i == self.selected {
+                    Line::from(format!("> {label}"))
+                        .bold()
+                        .bg(CONFIG.theme.highlight_bg)
+                } else {
+                    Line::from(format!("  {label}"))
+                }
+            })
+            .collect();
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(popup_block(" Duplicate torrent "), block_rect);
+        f.render_widget(
+            Paragraph::new(message).wrap(Wrap { trim: false }),
+            message_rect,
+        );
+        f.render_widget(Paragraph::new(choices), choices_rect);
+    }
+}
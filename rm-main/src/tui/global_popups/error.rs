@@ -2,7 +2,7 @@ use ratatui::{
     prelude::*,
     widgets::{Block, BorderType, Clear, Paragraph, Wrap},
 };
-
+use rm_config::CONFIG;
 use rm_shared::action::Action;
 
 use crate::tui::components::{popup_rects, Component, ComponentAction};
@@ -13,6 +13,7 @@ pub struct ErrorPopup {
     title: String,
     message: String,
     error: String,
+    button_rect: Rect,
 }
 
 impl ErrorPopup {
@@ -21,6 +22,7 @@ impl ErrorPopup {
             title,
             message,
             error,
+            button_rect: Rect::default(),
         }
     }
 }
@@ -30,6 +32,9 @@ impl Component for ErrorPopup {
         match action {
             _ if action.is_soft_quit() => ComponentAction::Quit,
             Action::Confirm => ComponentAction::Quit,
+            Action::Click(column, row) if self.button_rect.contains(Position::new(column, row)) => {
+                ComponentAction::Quit
+            }
             _ => ComponentAction::Nothing,
         }
     }
@@ -41,16 +46,22 @@ impl Component for ErrorPopup {
             .split(text_rect)[1];
 
         let button = Paragraph::new("[ OK ]").bold().right_aligned();
+        let [_, ok_rect] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(6)]).areas(button_rect);
+        self.button_rect = ok_rect;
 
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .title_style(Style::new().red())
+            .border_style(Style::default().fg(CONFIG.theme.border))
+            .title_style(Style::new().fg(CONFIG.theme.error))
             .title(format!(" {} ", self.title));
 
         let lines = vec![
             Line::from(self.message.as_str()),
             Line::default(),
-            Line::from(self.error.as_str()).red().on_black(),
+            Line::from(self.error.as_str())
+                .fg(CONFIG.theme.error)
+                .bg(CONFIG.theme.highlight_bg),
         ];
 
         let error_message = Paragraph::new(lines).wrap(Wrap { trim: false });
@@ -30,6 +30,7 @@ pub struct HelpPopup {
     torrent_keys: Vec<(String, &'static str)>,
     torrent_file_viewer_keys: Vec<(String, &'static str)>,
     search_keys: Vec<(String, &'static str)>,
+    input_keys: Vec<(String, &'static str)>,
     max_key_len: usize,
     max_line_len: usize,
 }
@@ -80,6 +81,10 @@ impl HelpPopup {
             .keybindings
             .search_tab
             .get_help_repr_with_override(override_keycode);
+        let input_keys = CONFIG
+            .keybindings
+            .input
+            .get_help_repr_with_override(override_keycode);
 
         let mut calc_max_lens = |keys: &[(String, &'static str)]| {
             for (keycode, desc) in keys {
@@ -99,6 +104,7 @@ impl HelpPopup {
         calc_max_lens(&global_keys);
         calc_max_lens(&torrent_keys);
         calc_max_lens(&search_keys);
+        calc_max_lens(&input_keys);
 
         debug_assert!(max_key_len > 0);
         debug_assert!(max_line_len > 0);
@@ -108,6 +114,7 @@ impl HelpPopup {
             torrent_keys,
             torrent_file_viewer_keys,
             search_keys,
+            input_keys,
             max_key_len,
             max_line_len,
         }
@@ -198,6 +205,7 @@ impl Component for HelpPopup {
         let torrent_keys = padded_keys(&mut self.torrent_keys);
         let torrent_file_viewer_keys = padded_keys(&mut self.torrent_file_viewer_keys);
         let search_keys = padded_keys(&mut self.search_keys);
+        let input_keys = padded_keys(&mut self.input_keys);
 
         let mut lines = vec![];
 
@@ -249,6 +257,16 @@ impl Component for HelpPopup {
 
         insert_keys(&mut lines, search_keys);
 
+        lines.push(
+            Line::from(vec![Span::styled(
+                "Text Input",
+                Style::default().bold().underlined(),
+            )])
+            .centered(),
+        );
+
+        insert_keys(&mut lines, input_keys);
+
         let help_text = Text::from(lines);
 
         if text_rect.height <= u16::try_from(help_text.lines.len()).unwrap() {
@@ -289,7 +307,7 @@ impl Component for HelpPopup {
 
         if let Some(scroll) = &mut self.scroll {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .thumb_style(Style::default().fg(CONFIG.general.accent_color));
+                .thumb_style(Style::default().fg(CONFIG.theme.accent));
 
             f.render_stateful_widget(
                 scrollbar,
@@ -1,8 +1,10 @@
+mod duplicate;
 mod error;
 mod help;
 
 use ratatui::prelude::*;
 
+pub use duplicate::DuplicatePopup;
 pub use error::ErrorPopup;
 pub use help::HelpPopup;
 
@@ -15,6 +17,7 @@ use super::{
 
 pub(super) struct GlobalPopupManager {
     pub error_popup: Option<ErrorPopup>,
+    pub duplicate_popup: Option<DuplicatePopup>,
     pub help_popup: Option<HelpPopup>,
 }
 
@@ -22,12 +25,13 @@ impl GlobalPopupManager {
     pub fn new() -> Self {
         Self {
             error_popup: None,
+            duplicate_popup: None,
             help_popup: None,
         }
     }
 
     pub const fn needs_action(&self) -> bool {
-        self.error_popup.is_some() || self.help_popup.is_some()
+        self.error_popup.is_some() || self.duplicate_popup.is_some() || self.help_popup.is_some()
     }
 
     pub fn toggle_help(&mut self) {
@@ -44,6 +48,11 @@ impl GlobalPopupManager {
                 self.error_popup = None;
                 CTX.send_action(Action::Render);
             }
+        } else if let Some(popup) = &mut self.duplicate_popup {
+            if popup.handle_actions(action).is_quit() {
+                self.duplicate_popup = None;
+                CTX.send_action(Action::Render);
+            }
         } else if let Some(popup) = &mut self.help_popup {
             if popup.handle_actions(action).is_quit() {
                 self.help_popup = None;
@@ -69,6 +78,8 @@ impl Component for GlobalPopupManager {
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         if let Some(popup) = &mut self.error_popup {
             popup.render(f, rect)
+        } else if let Some(popup) = &mut self.duplicate_popup {
+            popup.render(f, rect);
         } else if let Some(popup) = &mut self.help_popup {
             popup.render(f, rect);
         }
@@ -1,19 +1,19 @@
 use std::fmt::Display;
 
 use intui_tabs::{Tabs, TabsState};
-use ratatui::prelude::*;
+use ratatui::{layout::Flex, prelude::*};
 
 use rm_config::CONFIG;
 use rm_shared::{
     action::{Action, UpdateAction},
-    current_window::Window,
+    current_window::{TorrentWindow, Window},
 };
 
 use crate::tui::ctx::CTX;
 
 use super::{
-    components::{Component, ComponentAction},
-    global_popups::{ErrorPopup, GlobalPopupManager},
+    components::{tab_rects, Component, ComponentAction},
+    global_popups::{DuplicatePopup, ErrorPopup, GlobalPopupManager},
     tabs::{search::SearchTab, torrents::TorrentsTab},
 };
 
@@ -43,6 +43,8 @@ pub struct MainWindow {
     pub torrents_tab: TorrentsTab,
     pub search_tab: SearchTab,
     global_popup_manager: GlobalPopupManager,
+    top_bar_rect: Rect,
+    tab_rects: Vec<Rect>,
 }
 
 impl MainWindow {
@@ -52,9 +54,26 @@ impl MainWindow {
             torrents_tab: TorrentsTab::new(),
             search_tab: SearchTab::new(),
             global_popup_manager: GlobalPopupManager::new(),
+            top_bar_rect: Rect::default(),
+            tab_rects: vec![],
         }
     }
 
+    /// Titles of the tabs as the top bar shows them.
+    fn tab_titles() -> Vec<String> {
+        [CurrentTab::Torrents, CurrentTab::Search]
+            .iter()
+            .enumerate()
+            .map(|(idx, tab)| {
+                if CONFIG.general.beginner_mode {
+                    format!("{}. {tab}", idx + 1)
+                } else {
+                    tab.to_string()
+                }
+            })
+            .collect()
+    }
+
     pub fn current_window(&self) -> Window {
         match self.tabs.current() {
             CurrentTab::Torrents => Window::Torrents(self.torrents_tab.current_window),
@@ -74,6 +93,35 @@ impl Component for MainWindow {
             _ if self.global_popup_manager.needs_action() => {
                 self.global_popup_manager.handle_actions(action);
             }
+            // These popups use them to switch between their own tabs or directories.
+            A::Left | A::Right
+                if matches!(
+                    self.current_window(),
+                    Window::Torrents(
+                        TorrentWindow::Inspector
+                            | TorrentWindow::Statistics
+                            | TorrentWindow::TorrentPicker
+                            | TorrentWindow::ColumnEditor
+                    )
+                ) =>
+            {
+                self.torrents_tab.handle_actions(action);
+            }
+            A::Click(column, row) if row == self.top_bar_rect.y => {
+                let position = Position::new(column, row);
+                if let Some(idx) = self
+                    .tab_rects
+                    .iter()
+                    .position(|rect| rect.contains(position))
+                {
+                    self.tabs.set(idx + 1);
+                    CTX.send_action(Action::Render);
+                }
+            }
+            A::JumpToTorrent(_) => {
+                self.tabs.set(1);
+                self.torrents_tab.handle_actions(action);
+            }
             A::Left | A::ChangeTab(1) => {
                 if self.tabs.current() != CurrentTab::Torrents {
                     self.tabs.set(1);
@@ -105,6 +153,10 @@ impl Component for MainWindow {
                     ErrorPopup::new(err.title, err.description, err.source.to_string());
                 self.global_popup_manager.error_popup = Some(error_popup);
             }
+            UpdateAction::DuplicateTorrent(duplicate) => {
+                self.global_popup_manager.duplicate_popup = Some(DuplicatePopup::new(*duplicate));
+                CTX.send_action(Action::Render);
+            }
             action if self.tabs.current() == CurrentTab::Torrents => {
                 self.torrents_tab.handle_update_action(action)
             }
@@ -123,11 +175,27 @@ impl Component for MainWindow {
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         let [top_bar, main_window] =
             Layout::vertical([Constraint::Length(1), Constraint::Percentage(100)]).areas(rect);
+        self.top_bar_rect = top_bar;
+
+        // Centered here rather than by the widget, so that clicks can be
+        // hit-tested against the same area.
+        let titles = Self::tab_titles();
+        let tabs_width = titles
+            .iter()
+            .map(|title| Line::raw(title).width() + 3)
+            .sum::<usize>();
+        let [tabs_rect] = Layout::horizontal([Constraint::Length(
+            u16::try_from(tabs_width).unwrap_or(u16::MAX),
+        )])
+        .flex(Flex::Center)
+        .areas(top_bar);
+        self.tab_rects = tab_rects(tabs_rect, titles.iter().map(String::as_str));
 
         let tabs = Tabs::new()
             .beginner_mode(CONFIG.general.beginner_mode)
-            .color(CONFIG.general.accent_color);
-        f.render_stateful_widget(tabs, top_bar, &mut self.tabs);
+            .color(CONFIG.theme.accent)
+            .center(false);
+        f.render_stateful_widget(tabs, tabs_rect, &mut self.tabs);
 
         match self.tabs.current() {
             CurrentTab::Torrents => self.torrents_tab.render(f, main_window),
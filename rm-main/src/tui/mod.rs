@@ -3,4 +3,5 @@ mod components;
 pub mod ctx;
 mod global_popups;
 pub mod main_window;
+mod opener;
 pub mod tabs;
@@ -0,0 +1,42 @@
+use std::{
+    io,
+    process::{Command, Stdio},
+};
+
+use rm_config::CONFIG;
+
+/// Opens a file or a directory located on the daemon's machine.
+///
+/// The path is translated with `path_mappings` first and opened either with
+/// an opener configured for its extension or the system's default application.
+/// Returns the local path that was opened.
+pub fn open(remote_path: &str) -> io::Result<String> {
+    let path = CONFIG.connection.local_path(remote_path);
+
+    match CONFIG.general.opener_for(&path) {
+        Some(opener) => {
+            let (program, args) = opener.command.split_first().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "opener's command is empty")
+            })?;
+
+            let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+            if let Some(placeholder) = args.iter_mut().find(|arg| **arg == "{}") {
+                *placeholder = &path;
+            } else {
+                args.push(&path);
+            }
+
+            let mut child = Command::new(program)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()?;
+            // Reap the process once it exits so that it doesn't linger as a zombie.
+            std::thread::spawn(move || child.wait());
+        }
+        None => open::that_detached(&path)?,
+    }
+
+    Ok(path)
+}
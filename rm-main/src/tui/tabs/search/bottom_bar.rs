@@ -1,6 +1,6 @@
 use ratatui::{
     layout::Rect,
-    style::{Style, Stylize},
+    style::Style,
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
@@ -160,12 +160,12 @@ impl Component for SearchState {
                 );
                 let default_throbber = throbber_widgets_tui::Throbber::default()
                     .label(label)
-                    .style(ratatui::style::Style::default().fg(ratatui::style::Color::Yellow));
+                    .style(ratatui::style::Style::default().fg(CONFIG.theme.warning));
                 f.render_stateful_widget(default_throbber.clone(), rect, state);
             }
             SearchStage::NoResults => {
                 let mut line = Line::default();
-                line.push_span(Span::styled("", Style::default().red()));
+                line.push_span(Span::styled("", Style::default().fg(CONFIG.theme.error)));
                 line.push_span(Span::raw(" No results. "));
                 append_key_info(&mut line);
                 let paragraph = Paragraph::new(line);
@@ -173,7 +173,7 @@ impl Component for SearchState {
             }
             SearchStage::Found(count) => {
                 let mut line = Line::default();
-                line.push_span(Span::styled("", Style::default().green()));
+                line.push_span(Span::styled("", Style::default().fg(CONFIG.theme.success)));
                 line.push_span(Span::raw(format!(" Found {count}. ")));
                 append_key_info(&mut line);
                 let paragraph = Paragraph::new(line);
@@ -4,7 +4,7 @@ mod popups;
 use std::{borrow::Cow, sync::Arc, time::Duration};
 
 use bottom_bar::BottomBar;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent};
 use futures::{stream::FuturesUnordered, StreamExt};
 use magnetease::{Magnet, MagneteaseErrorKind, WhichProvider};
 use popups::{CurrentPopup, PopupManager};
@@ -16,10 +16,9 @@ use ratatui::{
 use reqwest::Client;
 use rm_config::CONFIG;
 use tokio::sync::mpsc::{self, UnboundedSender};
-use tui_input::{backend::crossterm::to_input_request, Input};
 
 use crate::tui::{
-    components::{Component, ComponentAction, GenericTable},
+    components::{Component, ComponentAction, GenericTable, InputManager},
     ctx::CTX,
 };
 use rm_shared::{
@@ -37,13 +36,14 @@ enum SearchTabFocus {
 pub(crate) struct SearchTab {
     pub current_window: SearchWindow,
     focus: SearchTabFocus,
-    input: Input,
+    input: InputManager,
     search_query_rx: UnboundedSender<String>,
     table: GenericTable<Magnet>,
     popup_manager: PopupManager,
     configured_providers: Vec<ConfiguredProvider>,
     bottom_bar: BottomBar,
     currently_displaying_no: u16,
+    table_rect: Rect,
 }
 
 impl SearchTab {
@@ -111,11 +111,12 @@ impl SearchTab {
         Self {
             current_window: SearchWindow::General,
             focus: SearchTabFocus::List,
-            input: Input::default(),
+            input: InputManager::new(String::new()).history("search"),
             table,
             bottom_bar,
             search_query_rx: search_query_tx,
             currently_displaying_no: 0,
+            table_rect: Rect::default(),
             popup_manager: PopupManager::new(),
             configured_providers,
         }
@@ -124,7 +125,7 @@ impl SearchTab {
     fn magnet_to_row(magnet: &Magnet) -> Row {
         let size = bytes_to_human_format(magnet.bytes as i64);
         Row::new([
-            Cell::from(Cow::Owned(magnet.seeders.to_string())).light_green(),
+            Cell::from(Cow::Owned(magnet.seeders.to_string())).fg(CONFIG.theme.seeders),
             Cell::from(Cow::Borrowed(&*magnet.title)),
             Cell::from(Cow::Owned(size)),
         ])
@@ -139,6 +140,18 @@ impl SearchTab {
         CTX.send_action(Action::Render);
     }
 
+    /// Highlights the magnet on this terminal row, returning whether there was one.
+    fn select_at(&mut self, row: u16) -> bool {
+        let has_header = !CONFIG.general.headers_hide;
+        let Some(idx) = self.table.item_at(self.table_rect, has_header, row) else {
+            return false;
+        };
+
+        self.table.state.borrow_mut().select(Some(idx));
+        CTX.send_action(Action::Render);
+        true
+    }
+
     fn add_magnet(&mut self) {
         let magnet_url = self.table.current_item().map(|magnet| magnet.url);
         if let Some(magnet_url) = magnet_url {
@@ -151,8 +164,9 @@ impl SearchTab {
 
         match input.code {
             KeyCode::Enter => {
-                if !self.input.to_string().is_empty() {
-                    self.search_query_rx.send(self.input.to_string()).unwrap();
+                if !self.input.text().is_empty() {
+                    self.input.save_history();
+                    self.search_query_rx.send(self.input.text()).unwrap();
                     self.focus = SearchTabFocus::List;
                     CTX.send_update_action(UpdateAction::SwitchToNormalMode);
                 }
@@ -162,15 +176,19 @@ impl SearchTab {
                 CTX.send_update_action(UpdateAction::SwitchToNormalMode);
             }
             _ => {
-                let event = Event::Key(input);
-                if let Some(req) = to_input_request(&event) {
-                    self.input.handle(req);
+                if self.input.handle_key(input).is_some() {
                     CTX.send_action(A::Render);
                 }
             }
         }
     }
 
+    fn handle_paste(&mut self, text: &str) {
+        if self.input.paste(text).is_some() {
+            CTX.send_action(Action::Render);
+        }
+    }
+
     fn start_search(&mut self) {
         self.focus = SearchTabFocus::Search;
         CTX.send_update_action(UpdateAction::SwitchToInputMode);
@@ -280,10 +298,12 @@ impl Component for SearchTab {
             A::Quit => CTX.send_action(Action::Quit),
             A::Search => self.start_search(),
             A::ChangeFocus => self.change_focus(),
-            A::Input(_) if self.bottom_bar.requires_input() => {
+            A::Input(_) | A::Paste(_) if self.bottom_bar.requires_input() => {
                 self.bottom_bar.handle_actions(action);
             }
             A::Input(input) => self.handle_input(input),
+            A::Paste(text) => self.handle_paste(&text),
+            A::PasteLink(link) => self.bottom_bar.add_magnet(link),
             A::Down => self.next_torrent(),
             A::Up => self.previous_torrent(),
             A::ScrollUpBy(amount) => self.scroll_up_by(amount),
@@ -293,6 +313,10 @@ impl Component for SearchTab {
             A::Home => self.scroll_to_home(),
             A::End => self.scroll_to_end(),
             A::Confirm => self.add_magnet(),
+            A::Click(_, row) => {
+                self.select_at(row);
+            }
+            A::DoubleClick(_, row) if self.select_at(row) => self.add_magnet(),
             A::XdgOpen => self.xdg_open(),
             A::ShowProvidersInfo => self.show_providers_info(),
 
@@ -373,6 +397,7 @@ impl Component for SearchTab {
         .areas(rect);
 
         self.currently_displaying_no = rest.height;
+        self.table_rect = rest;
 
         let search_rect = Layout::horizontal([
             Constraint::Percentage(25),
@@ -383,20 +408,18 @@ impl Component for SearchTab {
         .split(top_line)[1];
 
         let input = {
-            if self.input.value().is_empty() && self.focus != SearchTabFocus::Search {
-                "press / to search"
+            if self.input.text().is_empty() && self.focus != SearchTabFocus::Search {
+                String::from("press / to search")
             } else {
-                self.input.value()
+                self.input.text()
             }
         };
 
         let search_style = {
             if self.focus == SearchTabFocus::Search {
-                Style::default()
-                    .underlined()
-                    .fg(CONFIG.general.accent_color)
+                Style::default().underlined().fg(CONFIG.theme.accent)
             } else {
-                Style::default().underlined().gray()
+                Style::default().underlined().fg(CONFIG.theme.inactive)
             }
         };
 
@@ -427,9 +450,9 @@ impl Component for SearchTab {
         ];
 
         let table_higlight_style = Style::default()
-            .on_black()
+            .bg(CONFIG.theme.highlight_bg)
             .bold()
-            .fg(CONFIG.general.accent_color);
+            .fg(CONFIG.theme.accent);
 
         let table = {
             let table = Table::new(items, widths).row_highlight_style(table_higlight_style);
@@ -23,11 +23,19 @@ impl From<&ConfiguredProvider> for Row<'_> {
     fn from(value: &ConfiguredProvider) -> Self {
         let mut name: Line = match value.provider_state {
             _ if !value.enabled => format!(" {} ", CONFIG.icons.provider_disabled).into(),
-            ProviderState::Idle => format!(" {} ", CONFIG.icons.idle).yellow().into(),
-            ProviderState::Searching => format!(" {} ", CONFIG.icons.searching).yellow().into(),
-            ProviderState::Found(_) => format!(" {} ", CONFIG.icons.success).green().into(),
+            ProviderState::Idle => format!(" {} ", CONFIG.icons.idle)
+                .fg(CONFIG.theme.warning)
+                .into(),
+            ProviderState::Searching => format!(" {} ", CONFIG.icons.searching)
+                .fg(CONFIG.theme.warning)
+                .into(),
+            ProviderState::Found(_) => format!(" {} ", CONFIG.icons.success)
+                .fg(CONFIG.theme.success)
+                .into(),
             ProviderState::Error(_) | ProviderState::Timeout => {
-                format!(" {} ", CONFIG.icons.failure).red().into()
+                format!(" {} ", CONFIG.icons.failure)
+                    .fg(CONFIG.theme.error)
+                    .into()
             }
         };
 
@@ -48,17 +56,17 @@ impl From<&ConfiguredProvider> for Row<'_> {
             _ if !value.enabled => "Disabled".into(),
             ProviderState::Idle => "Idle".into(),
             ProviderState::Searching => format!("{} Searching...", CONFIG.icons.searching)
-                .yellow()
+                .fg(CONFIG.theme.warning)
                 .into(),
             ProviderState::Found(count) => {
                 let mut line = Line::default();
                 line.push_span("Found(");
-                line.push_span(count.to_string().green());
+                line.push_span(count.to_string().fg(CONFIG.theme.success));
                 line.push_span(")");
                 line
             }
-            ProviderState::Timeout => "Timeout".red().into(),
-            ProviderState::Error(e) => e.to_string().red().into(),
+            ProviderState::Timeout => "Timeout".fg(CONFIG.theme.error).into(),
+            ProviderState::Error(e) => e.to_string().fg(CONFIG.theme.error).into(),
         };
 
         let row = Row::new(vec![name, url, category.into(), status]);
@@ -66,7 +74,7 @@ impl From<&ConfiguredProvider> for Row<'_> {
         if value.enabled {
             row
         } else {
-            row.dark_gray()
+            row.fg(CONFIG.theme.dim)
         }
     }
 }
@@ -93,9 +101,10 @@ impl Component for ProvidersPopup {
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         let (popup_rect, block_rect, table_rect) = popup_rects(rect, 80, 50);
 
-        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let title_style = Style::default().fg(CONFIG.theme.accent);
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CONFIG.theme.border))
             .title(Title::from(" Providers ".set_style(title_style)))
             .title_bottom(popup_close_button_highlight());
 
@@ -7,12 +7,18 @@ pub mod tasks;
 
 use std::sync::OnceLock;
 
+use tokio::sync::mpsc::{self, UnboundedSender};
+
 use crate::transmission::TorrentAction;
-use crate::tui::components::{Component, ComponentAction};
+use crate::tui::components::{table_column_at, Component, ComponentAction};
 use crate::tui::ctx::CTX;
+use crate::tui::opener;
 
-use popups::details::DetailsPopup;
+use popups::add_preview::AddPreviewPopup;
+use popups::columns::ColumnEditorPopup;
+use popups::inspector::InspectorPopup;
 use popups::stats::StatisticsPopup;
+use popups::torrent_picker::TorrentPickerPopup;
 use ratatui::{
     prelude::*,
     widgets::{Cell, Row, Table},
@@ -25,10 +31,11 @@ use rm_shared::{
     status_task::StatusTask,
 };
 use rustmission_torrent::RustmissionTorrent;
-use tasks::TorrentSelection;
+use tasks::{LabelEdit, TorrentSelection};
 use transmission_rpc::types::{Id, SessionGet, TorrentStatus};
 
 use crate::transmission;
+use crate::transmission::new_torrents::TorrentLabels;
 
 use self::bottom_stats::BottomStats;
 use self::popups::files::FilesPopup;
@@ -40,10 +47,12 @@ pub static SESSION_GET: OnceLock<SessionGet> = OnceLock::new();
 
 pub struct TorrentsTab {
     pub current_window: TorrentWindow,
+    new_torrents_tx: Option<UnboundedSender<Vec<TorrentLabels>>>,
     table_manager: TableManager,
     popup_manager: PopupManager,
     task_manager: TaskManager,
     bottom_stats: BottomStats,
+    table_rect: Rect,
 }
 
 impl TorrentsTab {
@@ -55,12 +64,30 @@ impl TorrentsTab {
         tokio::spawn(transmission::fetchers::torrents());
         tokio::spawn(transmission::fetchers::free_space());
 
+        let new_torrent_handlers: Vec<_> = [
+            transmission::auto_categorize::handler(),
+            transmission::auto_unwant::handler(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let new_torrents_tx = if new_torrent_handlers.is_empty() {
+            None
+        } else {
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(transmission::new_torrents::watch(new_torrent_handlers, rx));
+            Some(tx)
+        };
+
         Self {
             bottom_stats,
             task_manager: TaskManager::new(),
             table_manager,
             popup_manager: PopupManager::new(),
             current_window: TorrentWindow::General,
+            new_torrents_tx,
+            table_rect: Rect::default(),
         }
     }
 }
@@ -81,6 +108,11 @@ impl Component for TorrentsTab {
 
         if self.popup_manager.is_showing_popup() {
             self.popup_manager.handle_actions(action);
+            if let Some(CurrentPopup::ColumnEditor(popup)) = &mut self.popup_manager.current_popup {
+                if let Some(layout) = popup.take_changed_layout() {
+                    self.table_manager.set_layout(layout);
+                }
+            }
             return ComponentAction::Nothing;
         }
 
@@ -108,18 +140,51 @@ impl Component for TorrentsTab {
                     self.task_manager.default();
                     CTX.send_action(Action::Render);
                 }
+                A::Click(column, row) => {
+                    if let Some(header) = self.header_at(column, row) {
+                        self.table_manager.sort_by_column(header);
+                        self.task_manager.default();
+                        CTX.send_action(Action::Render);
+                    }
+                }
                 _ => (),
             }
             return ComponentAction::Nothing;
         }
 
+        if self.table_manager.is_visual_selecting() {
+            match action {
+                A::Close => {
+                    self.table_manager.cancel_visual_selection();
+                    self.update_selection_task();
+                    CTX.send_action(Action::Render);
+                    return ComponentAction::Nothing;
+                }
+                A::VisualSelect => {
+                    self.table_manager.confirm_visual_selection();
+                    self.update_selection_task();
+                    CTX.send_action(Action::Render);
+                    return ComponentAction::Nothing;
+                }
+                A::Up
+                | A::Down
+                | A::ScrollUpPage
+                | A::ScrollDownPage
+                | A::ScrollUpBy(_)
+                | A::ScrollDownBy(_)
+                | A::Home
+                | A::End
+                | A::Click(..) => (),
+                // Acting on the range ends the visual mode.
+                _ => {
+                    self.table_manager.confirm_visual_selection();
+                    self.update_selection_task();
+                }
+            }
+        }
+
         if !self.table_manager.selected_torrents_ids.is_empty() && action.is_soft_quit() {
-            self.table_manager
-                .table
-                .items
-                .iter_mut()
-                .for_each(|t| t.is_selected = false);
-            self.table_manager.selected_torrents_ids.drain(..);
+            self.table_manager.clear_selection();
             self.task_manager.default();
             CTX.send_action(Action::Render);
             return ComponentAction::Nothing;
@@ -139,20 +204,50 @@ impl Component for TorrentsTab {
             A::ScrollDownBy(amount) => self.scroll_down_by(amount),
             A::Home => self.select_first(),
             A::End => self.select_last(),
+            A::Click(column, row) => self.click(column, row),
+            A::DoubleClick(column, row) => {
+                if self.torrent_at(column, row).is_some() {
+                    self.confirm();
+                }
+            }
             A::ShowStats => self.show_statistics_popup(),
             A::ShowFiles => self.show_files_popup(),
-            A::Confirm => self.show_details_popup(),
+            A::Confirm => self.confirm(),
+            A::CycleGrouping => {
+                self.table_manager.cycle_grouping();
+                self.bottom_stats
+                    .update_selected_indicator(&self.table_manager);
+                CTX.send_action(Action::Render);
+            }
+            A::ToggleGroups => {
+                self.table_manager.toggle_all_groups();
+                self.bottom_stats
+                    .update_selected_indicator(&self.table_manager);
+                CTX.send_action(Action::Render);
+            }
             A::Select => {
                 self.table_manager.select_current_torrent();
-                if !self.table_manager.selected_torrents_ids.is_empty() {
-                    self.task_manager
-                        .select(self.table_manager.selected_torrents_ids.len());
-                } else {
-                    self.task_manager.default();
-                }
+                self.update_selection_task();
                 CTX.send_action(Action::Render);
             }
-            A::Pause => self.pause_current_torrent(),
+            A::VisualSelect => {
+                self.table_manager.start_visual_selection();
+                self.update_selection_task();
+                CTX.send_action(Action::Render);
+            }
+            A::SelectAll => {
+                self.table_manager.select_all();
+                self.update_selection_task();
+                CTX.send_action(Action::Render);
+            }
+            A::InvertSelection => {
+                self.table_manager.invert_selection();
+                self.update_selection_task();
+                CTX.send_action(Action::Render);
+            }
+            A::Pause => self.pause_torrents(),
+            A::PauseAll => self.pause_all_torrents(true),
+            A::ResumeAll => self.pause_all_torrents(false),
             A::Delete => {
                 if let Some(torrent_selection) = self.get_currently_selected() {
                     self.task_manager.delete_torrents(torrent_selection);
@@ -164,7 +259,20 @@ impl Component for TorrentsTab {
                     self.task_manager.rename(id, curr_name);
                 }
             }
-            A::AddMagnet => self.task_manager.add_magnet(),
+            A::AddMagnet => self
+                .task_manager
+                .add_magnet(self.table_manager.all_download_dirs()),
+            A::AddTorrentFile => self.show_torrent_picker_popup(),
+            A::EditColumns => self.show_column_editor_popup(),
+            A::JumpToTorrent(id) => {
+                self.table_manager.highlight_torrent(id);
+                self.bottom_stats
+                    .update_selected_indicator(&self.table_manager);
+                CTX.send_action(Action::Render);
+            }
+            A::PasteLink(link) => self
+                .task_manager
+                .paste_magnet(link, self.table_manager.all_download_dirs()),
             A::Search => self.task_manager.search(
                 &self
                     .table_manager
@@ -175,8 +283,11 @@ impl Component for TorrentsTab {
             A::MoveTorrent => {
                 if let Some(selection) = self.get_currently_selected() {
                     if let Some(session_info) = SESSION_GET.get() {
-                        self.task_manager
-                            .move_torrent(selection, session_info.download_dir.clone());
+                        self.task_manager.move_torrent(
+                            selection,
+                            session_info.download_dir.clone(),
+                            self.table_manager.all_download_dirs(),
+                        );
                     }
                 }
             }
@@ -185,6 +296,20 @@ impl Component for TorrentsTab {
                     self.task_manager.change_category(selection);
                 }
             }
+            A::AddLabel => {
+                if let Some(selection) = self.get_currently_selected() {
+                    let autocompletions = self.table_manager.all_labels();
+                    self.task_manager
+                        .edit_label(selection, LabelEdit::Add, autocompletions);
+                }
+            }
+            A::RemoveLabel => {
+                if let Some(selection) = self.get_currently_selected() {
+                    let autocompletions = self.table_manager.labels_of(&selection.ids());
+                    self.task_manager
+                        .edit_label(selection, LabelEdit::Remove, autocompletions);
+                }
+            }
             A::XdgOpen => self.xdg_open_current_torrent(),
             A::MoveToColumnLeft | A::MoveToColumnRight => {
                 self.table_manager.enter_sorting_selection();
@@ -204,9 +329,21 @@ impl Component for TorrentsTab {
             UpdateAction::ChangeTorrentWindow(window) => {
                 self.current_window = window;
             }
+            UpdateAction::PreviewTorrent(pending) => {
+                let popup = AddPreviewPopup::new(*pending);
+                if !popup.has_files() {
+                    popup.add();
+                    return;
+                }
+                self.popup_manager
+                    .show_popup(CurrentPopup::AddPreview(popup));
+
+                let update_action = UpdateAction::ChangeTorrentWindow(TorrentWindow::FileViewer);
+                CTX.send_update_action(update_action);
+            }
             UpdateAction::SessionStats(stats) => {
                 if let Some(CurrentPopup::Stats(popup)) = &mut self.popup_manager.current_popup {
-                    popup.update_stats(&stats)
+                    popup.update_stats(stats.clone())
                 }
                 self.bottom_stats.set_stats(stats);
             }
@@ -220,6 +357,9 @@ impl Component for TorrentsTab {
                 self.bottom_stats
                     .update_selected_indicator(&self.table_manager);
             }
+            UpdateAction::SearchFilterSelectAll => {
+                self.table_manager.select_all();
+            }
             UpdateAction::SearchFilterClear => {
                 self.table_manager.filter = None;
                 self.table_manager.table.state.borrow_mut().select(Some(0));
@@ -228,13 +368,25 @@ impl Component for TorrentsTab {
                     .update_selected_indicator(&self.table_manager);
             }
             UpdateAction::UpdateTorrents(torrents) => {
+                if let Some(new_torrents_tx) = &self.new_torrents_tx {
+                    let labels = torrents
+                        .iter()
+                        .filter_map(|t| Some((t.id()?, t.labels.clone().unwrap_or_default())))
+                        .collect();
+                    let _ = new_torrents_tx.send(labels);
+                }
+
                 let torrents = torrents.into_iter().map(RustmissionTorrent::from).collect();
 
                 self.table_manager.set_new_rows(torrents);
-                if self.table_manager.selected_torrents_ids.is_empty()
-                    && self.task_manager.is_selection_task()
+                if let Some(CurrentPopup::Inspector(popup)) = &mut self.popup_manager.current_popup
                 {
-                    self.task_manager.default()
+                    if let Some(history) = self.table_manager.speed_history_of(popup.torrent_id()) {
+                        popup.set_speed_history(history.clone());
+                    }
+                }
+                if self.task_manager.is_selection_task() {
+                    self.update_selection_task();
                 }
 
                 self.bottom_stats
@@ -248,12 +400,7 @@ impl Component for TorrentsTab {
                     return;
                 }
 
-                if !self.table_manager.selected_torrents_ids.is_empty() {
-                    self.task_manager
-                        .select(self.table_manager.selected_torrents_ids.len());
-                } else {
-                    self.task_manager.default();
-                }
+                self.update_selection_task();
                 CTX.send_update_action(UpdateAction::SwitchToNormalMode);
             }
             other => self.task_manager.handle_update_action(other),
@@ -267,28 +414,24 @@ impl Component for TorrentsTab {
 
 impl TorrentsTab {
     fn render_table(&mut self, f: &mut Frame, rect: Rect) {
+        self.table_rect = rect;
         let offset = self.table_manager.table.state.borrow().offset();
         let mut torrents_displaying_no = 0;
         let mut space_left = rect.height;
-        for torrent in self.table_manager.table.items.iter().skip(offset) {
+        for height in self.table_manager.row_heights().skip(offset) {
             if space_left == 0 {
                 break;
             }
 
-            if torrent.error.is_some() {
-                space_left = space_left.saturating_sub(2);
-            } else {
-                space_left -= 1;
-            }
-
+            space_left = space_left.saturating_sub(height);
             torrents_displaying_no += 1;
         }
         self.table_manager.torrents_displaying_no = torrents_displaying_no;
 
         let highlight_table_style = Style::default()
-            .on_black()
+            .bg(CONFIG.theme.highlight_bg)
             .bold()
-            .fg(CONFIG.general.accent_color);
+            .fg(CONFIG.theme.accent);
 
         let rows = self.table_manager.rows();
 
@@ -296,7 +439,7 @@ impl TorrentsTab {
             .table_manager
             .headers()
             .iter()
-            .map(|h| h.header_name())
+            .map(|h| CONFIG.torrents_tab.header_name(h))
             .collect::<Vec<_>>();
 
         let sorted_header_name;
@@ -321,7 +464,7 @@ impl TorrentsTab {
             if self.table_manager.sorting_is_being_selected {
                 headers[sort_header] = headers[sort_header]
                     .clone()
-                    .style(Style::default().fg(CONFIG.general.accent_color));
+                    .style(Style::default().fg(CONFIG.theme.accent));
             }
         }
 
@@ -352,6 +495,10 @@ impl TorrentsTab {
                     .map(Id::Id)
                     .collect(),
             ))
+        } else if let Some(ids) = self.table_manager.current_group_ids() {
+            Some(TorrentSelection::Many(
+                ids.into_iter().map(Id::Id).collect(),
+            ))
         } else if let Some(t) = self.table_manager.current_torrent() {
             Some(TorrentSelection::Single(
                 t.id.clone(),
@@ -362,6 +509,17 @@ impl TorrentsTab {
         }
     }
 
+    /// Collapses or expands the highlighted group, or inspects the highlighted torrent.
+    fn confirm(&mut self) {
+        if self.table_manager.toggle_current_group() {
+            self.bottom_stats
+                .update_selected_indicator(&self.table_manager);
+            CTX.send_action(Action::Render);
+        } else {
+            self.show_inspector_popup();
+        }
+    }
+
     fn show_files_popup(&mut self) {
         if let Some(highlighted_torrent) = self.table_manager.current_torrent() {
             let popup = FilesPopup::new(highlighted_torrent.id.clone());
@@ -372,33 +530,119 @@ impl TorrentsTab {
         }
     }
 
-    fn show_details_popup(&mut self) {
+    fn show_inspector_popup(&mut self) {
         if let Some(highlighted_torrent) = self.table_manager.current_torrent() {
-            let popup = DetailsPopup::new(highlighted_torrent.clone());
-            self.popup_manager.show_popup(CurrentPopup::Details(popup));
+            let popup = InspectorPopup::new(highlighted_torrent.clone());
+            self.popup_manager
+                .show_popup(CurrentPopup::Inspector(popup));
+
+            let update_action = UpdateAction::ChangeTorrentWindow(TorrentWindow::Inspector);
+            CTX.send_update_action(update_action);
             CTX.send_action(Action::Render);
         }
     }
 
+    fn show_torrent_picker_popup(&mut self) {
+        let popup = TorrentPickerPopup::new();
+        self.popup_manager
+            .show_popup(CurrentPopup::TorrentPicker(popup));
+
+        let update_action = UpdateAction::ChangeTorrentWindow(TorrentWindow::TorrentPicker);
+        CTX.send_update_action(update_action);
+    }
+
+    fn show_column_editor_popup(&mut self) {
+        let popup = ColumnEditorPopup::new(self.table_manager.layout());
+        self.popup_manager
+            .show_popup(CurrentPopup::ColumnEditor(popup));
+
+        let update_action = UpdateAction::ChangeTorrentWindow(TorrentWindow::ColumnEditor);
+        CTX.send_update_action(update_action);
+        CTX.send_action(Action::Render);
+    }
+
     fn show_statistics_popup(&mut self) {
         if let Some(stats) = &self.bottom_stats.stats {
             let popup = StatisticsPopup::new(stats.clone());
             self.popup_manager.show_popup(CurrentPopup::Stats(popup));
+
+            let update_action = UpdateAction::ChangeTorrentWindow(TorrentWindow::Statistics);
+            CTX.send_update_action(update_action);
             CTX.send_action(Action::Render)
         }
     }
 
+    fn update_visual_selection(&mut self) {
+        if self.table_manager.is_visual_selecting() {
+            self.table_manager.update_visual_selection();
+            self.update_selection_task();
+        }
+    }
+
+    fn update_selection_task(&mut self) {
+        if self.table_manager.selected_torrents_ids.is_empty() {
+            self.task_manager.default();
+        } else {
+            self.task_manager.select(
+                self.table_manager.selected_torrents_ids.len(),
+                self.table_manager.selection_size(),
+                self.table_manager.is_visual_selecting(),
+            );
+        }
+    }
+
     fn previous_torrent(&mut self) {
         self.table_manager.table.previous();
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
+    /// Column of the header under this terminal cell, if headers are shown.
+    fn header_at(&self, column: u16, row: u16) -> Option<usize> {
+        if CONFIG.general.headers_hide || row != self.table_rect.y {
+            return None;
+        }
+        table_column_at(&self.table_manager.widths, self.table_rect, column)
+    }
+
+    /// Index (among the listed torrents) of the torrent under this terminal cell.
+    fn torrent_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.table_rect.contains(Position::new(column, row)) {
+            return None;
+        }
+
+        let mut body = self.table_rect;
+        if !CONFIG.general.headers_hide {
+            body.y += 1;
+            body.height = body.height.saturating_sub(1);
+        }
+        self.table_manager.listed_index_at(body, row)
+    }
+
+    fn click(&mut self, column: u16, row: u16) {
+        if let Some(header) = self.header_at(column, row) {
+            self.table_manager.sort_by_column(header);
+            CTX.send_action(Action::Render);
+        } else if let Some(idx) = self.torrent_at(column, row) {
+            self.table_manager
+                .table
+                .state
+                .borrow_mut()
+                .select(Some(idx));
+            self.bottom_stats
+                .update_selected_indicator(&self.table_manager);
+            self.update_visual_selection();
+            CTX.send_action(Action::Render);
+        }
+    }
+
     fn next_torrent(&mut self) {
         self.table_manager.table.next();
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
@@ -406,6 +650,7 @@ impl TorrentsTab {
         self.table_manager.table.scroll_up_by(usize::from(amount));
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
@@ -413,6 +658,7 @@ impl TorrentsTab {
         self.table_manager.table.scroll_down_by(usize::from(amount));
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
@@ -421,6 +667,7 @@ impl TorrentsTab {
         self.table_manager.table.scroll_down_by(scroll_by as usize);
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
@@ -429,6 +676,7 @@ impl TorrentsTab {
         self.table_manager.table.scroll_up_by(scroll_by as usize);
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
@@ -436,6 +684,7 @@ impl TorrentsTab {
         self.table_manager.table.select_first();
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
@@ -443,38 +692,107 @@ impl TorrentsTab {
         self.table_manager.table.select_last();
         self.bottom_stats
             .update_selected_indicator(&self.table_manager);
+        self.update_visual_selection();
         CTX.send_action(Action::Render);
     }
 
-    fn pause_current_torrent(&mut self) {
-        if let Some(torrent) = self.table_manager.current_torrent() {
-            let torrent_id = torrent.id.clone();
-            match torrent.status() {
-                TorrentStatus::Stopped => {
-                    CTX.send_torrent_action(TorrentAction::Start(vec![torrent_id]));
+    fn pause_torrents(&mut self) {
+        // The selection goes first, then the highlighted group.
+        let group_ids = if self.table_manager.selected_torrents_ids.is_empty() {
+            let Some(group_ids) = self.table_manager.current_group_ids() else {
+                self.pause_current_torrent();
+                return;
+            };
+            Some(group_ids)
+        } else {
+            None
+        };
+
+        // Pause the torrents if anything among them is running, resume otherwise.
+        let torrents: Vec<_> = self
+            .table_manager
+            .table
+            .items
+            .iter_mut()
+            .filter(|t| match (&group_ids, &t.id) {
+                (Some(group_ids), Id::Id(id)) => group_ids.contains(id),
+                (Some(_), Id::Hash(_)) => false,
+                (None, _) => t.is_selected,
+            })
+            .collect();
+        let pause = torrents
+            .iter()
+            .any(|t| t.status() != TorrentStatus::Stopped);
+
+        let ids = Self::set_paused(torrents, pause);
+        let what = format!("{} torrents", ids.len());
+        Self::send_pause_action(ids, pause, what);
+    }
+
+    fn pause_all_torrents(&mut self, pause: bool) {
+        let torrents = self.table_manager.table.items.iter_mut().collect();
+        let ids = Self::set_paused(torrents, pause);
+        Self::send_pause_action(ids, pause, "all torrents".to_string());
+    }
+
+    /// Updates statuses of torrents that will change and returns their IDs.
+    fn set_paused(torrents: Vec<&mut RustmissionTorrent>, pause: bool) -> Vec<Id> {
+        let mut ids = vec![];
+        for torrent in torrents {
+            let is_stopped = torrent.status() == TorrentStatus::Stopped;
+            if pause && !is_stopped {
+                torrent.update_status(TorrentStatus::Stopped);
+            } else if !pause && is_stopped {
+                // Complete torrents go back to seeding.
+                if torrent.progress >= 1.0 {
+                    torrent.update_status(TorrentStatus::Seeding);
+                } else {
                     torrent.update_status(TorrentStatus::Downloading);
-                    CTX.send_action(Action::Render);
-                }
-                _ => {
-                    CTX.send_torrent_action(TorrentAction::Stop(vec![torrent_id]));
-                    torrent.update_status(TorrentStatus::Stopped);
-                    CTX.send_action(Action::Render);
                 }
+            } else {
+                continue;
             }
+            ids.push(torrent.id.clone());
+        }
+        ids
+    }
+
+    fn send_pause_action(ids: Vec<Id>, pause: bool, what: String) {
+        if ids.is_empty() {
+            return;
+        }
+
+        let (task, action) = if pause {
+            (StatusTask::new_pause(what), TorrentAction::Stop(ids))
+        } else {
+            (StatusTask::new_resume(what), TorrentAction::Start(ids))
+        };
+
+        CTX.send_update_action(UpdateAction::StatusTaskSet(task));
+        CTX.send_torrent_action(action);
+        CTX.send_action(Action::Render);
+    }
+
+    fn pause_current_torrent(&mut self) {
+        if let Some(torrent) = self.table_manager.current_torrent() {
+            let pause = torrent.status() != TorrentStatus::Stopped;
+            let what = torrent.torrent_name.clone();
+            let ids = Self::set_paused(vec![torrent], pause);
+            Self::send_pause_action(ids, pause, what);
         }
     }
 
     fn xdg_open_current_torrent(&mut self) {
         if let Some(torrent) = self.table_manager.current_torrent() {
             let torrent_location = torrent.torrent_location();
-            match open::that_detached(&torrent_location) {
-                Ok(()) => CTX.send_update_action(UpdateAction::StatusTaskSetSuccess(
-                    StatusTask::new_open(torrent_location),
+            match opener::open(&torrent_location) {
+                Ok(path) => CTX.send_update_action(UpdateAction::StatusTaskSetSuccess(
+                    StatusTask::new_open(path),
                 )),
                 Err(err) => {
                     let desc = format!(
                         "Encountered an error while trying to open \"{}\"",
-                        torrent_location
+                        CONFIG.connection.local_path(&torrent_location)
                     );
                     let err_msg = ErrorMessage::new(
                         "Failed to open a torrent directory",
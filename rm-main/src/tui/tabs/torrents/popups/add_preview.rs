@@ -0,0 +1,296 @@
+use base64::Engine;
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Paragraph},
+};
+use rm_config::{
+    keymap::{actions::torrents_tab_file_viewer::TorrentsFileViewerAction, GeneralAction},
+    CONFIG,
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    metainfo::PendingTorrent,
+    status_task::StatusTask,
+    utils::bytes_to_human_format,
+};
+use transmission_rpc::types::{Priority, TorrentAddArgs};
+use tui_tree_widget::{Tree, TreeState};
+
+use super::files::Node;
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        components::{keybinding_style, popup_block, popup_rects, Component, ComponentAction},
+        ctx::CTX,
+    },
+};
+
+const MAX_TRACKERS_SHOWN: usize = 3;
+
+/// Shows what a .torrent file contains and lets the user pick its files before adding it.
+pub struct AddPreviewPopup {
+    pending: PendingTorrent,
+    wanted: Vec<bool>,
+    priorities: Vec<Priority>,
+    tree: Node,
+    tree_state: TreeState<String>,
+}
+
+impl AddPreviewPopup {
+    pub fn new(pending: PendingTorrent) -> Self {
+        let files_no = pending.metainfo.files.len();
+        let tree = Node::new_from_metainfo(&pending.metainfo);
+
+        let mut tree_state = TreeState::default();
+        // Torrents with many files keep them in a directory named after the torrent.
+        tree_state.open(vec![pending.metainfo.name.clone()]);
+
+        Self {
+            pending,
+            wanted: vec![true; files_no],
+            priorities: vec![Priority::Normal; files_no],
+            tree,
+            tree_state,
+        }
+    }
+
+    /// Files of the highlighted row, all files inside if it's a directory.
+    fn selected_ids(&self) -> Vec<usize> {
+        let selected = self.tree_state.selected();
+        match selected.last().and_then(|id| id.parse().ok()) {
+            Some(id) => vec![id],
+            None => self.tree.ids_in(selected),
+        }
+    }
+
+    fn toggle_wanted(&mut self) {
+        let ids = self.selected_ids();
+        let wanted = !ids.iter().all(|id| self.wanted[*id]);
+
+        for id in &ids {
+            self.wanted[*id] = wanted;
+        }
+        for file in self.tree.get_by_ids(&ids) {
+            file.set_wanted(wanted);
+        }
+    }
+
+    fn cycle_priority(&mut self) {
+        let ids = self.selected_ids();
+        let Some(first) = ids.first() else {
+            return;
+        };
+
+        let priority = match self.priorities[*first] {
+            Priority::Low => Priority::Normal,
+            Priority::Normal => Priority::High,
+            Priority::High => Priority::Low,
+        };
+        for id in &ids {
+            self.priorities[*id] = priority;
+        }
+        for file in self.tree.get_by_ids(&ids) {
+            file.set_priority(priority);
+        }
+    }
+
+    /// Whether there are files to pick, v2-only torrents can only be added as they are.
+    pub fn has_files(&self) -> bool {
+        !self.wanted.is_empty()
+    }
+
+    pub fn add(&self) {
+        let ids_where = |pred: &dyn Fn(usize) -> bool| -> Option<Vec<i32>> {
+            let ids: Vec<i32> = (0..self.wanted.len())
+                .filter(|id| pred(*id))
+                .filter_map(|id| i32::try_from(id).ok())
+                .collect();
+            (!ids.is_empty()).then_some(ids)
+        };
+
+        let args = TorrentAddArgs {
+            metainfo: Some(
+                base64::engine::general_purpose::STANDARD.encode(&self.pending.contents),
+            ),
+            download_dir: self.pending.download_dir.clone(),
+            labels: self.pending.category.clone().map(|category| vec![category]),
+            files_unwanted: ids_where(&|id| !self.wanted[id]),
+            priority_high: ids_where(&|id| self.priorities[id] == Priority::High),
+            priority_low: ids_where(&|id| self.priorities[id] == Priority::Low),
+            ..Default::default()
+        };
+
+        let name = self.pending.metainfo.name.clone();
+        CTX.send_update_action(UpdateAction::StatusTaskSet(StatusTask::new_add(
+            name.clone(),
+        )));
+        CTX.send_torrent_action(TorrentAction::AddWithArgs(name, Box::new(args)));
+    }
+
+    fn info_lines(&self) -> Vec<Line<'_>> {
+        let metainfo = &self.pending.metainfo;
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+
+        let wanted_size: i64 = metainfo
+            .files
+            .iter()
+            .zip(&self.wanted)
+            .filter(|(_, wanted)| **wanted)
+            .map(|(file, _)| file.length)
+            .sum();
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Size: ", highlight_style),
+            Span::raw(format!(
+                "{} of {} in {} files",
+                bytes_to_human_format(wanted_size),
+                bytes_to_human_format(metainfo.total_size()),
+                metainfo.files.len()
+            )),
+        ])];
+
+        if let Some(dir) = &self.pending.download_dir {
+            lines.push(Line::from(vec![
+                Span::styled("Directory: ", highlight_style),
+                Span::raw(dir.as_str()),
+            ]));
+        }
+
+        if let Some(comment) = metainfo.comment.as_ref().filter(|c| !c.is_empty()) {
+            lines.push(Line::from(vec![
+                Span::styled("Comment: ", highlight_style),
+                Span::raw(comment.as_str()),
+            ]));
+        }
+
+        let mut trackers = metainfo.trackers.iter().take(MAX_TRACKERS_SHOWN);
+        let first_tracker = trackers.next().map_or("none", String::as_str);
+        lines.push(Line::from(vec![
+            Span::styled("Trackers: ", highlight_style),
+            Span::raw(first_tracker),
+        ]));
+        for tracker in trackers {
+            lines.push(Line::from(format!("          {tracker}")));
+        }
+        let hidden = metainfo.trackers.len().saturating_sub(MAX_TRACKERS_SHOWN);
+        if hidden > 0 {
+            lines.push(Line::from(format!("          ...and {hidden} more")).fg(CONFIG.theme.dim));
+        }
+
+        lines
+    }
+
+    fn keybinding_tip() -> Line<'static> {
+        if !CONFIG.general.beginner_mode {
+            return Line::default();
+        }
+
+        let mut keys = vec![];
+
+        if let Some(key) = CONFIG
+            .keybindings
+            .general
+            .get_keys_for_action_joined(GeneralAction::Select)
+        {
+            keys.push(Span::raw(" "));
+            keys.push(Span::styled(key, keybinding_style()));
+            keys.push(Span::raw(" - toggle | "));
+        }
+
+        if let Some(key) = CONFIG
+            .keybindings
+            .torrents_tab_file_viewer
+            .get_keys_for_action_joined(TorrentsFileViewerAction::ChangeFilePriority)
+        {
+            keys.push(Span::styled(key, keybinding_style()));
+            keys.push(Span::raw(" - change priority | "));
+        }
+
+        if let Some(key) = CONFIG
+            .keybindings
+            .general
+            .get_keys_for_action_joined(GeneralAction::Confirm)
+        {
+            keys.push(Span::styled(key, keybinding_style()));
+            keys.push(Span::raw(" - add "));
+        }
+
+        Line::from(keys)
+    }
+}
+
+impl Component for AddPreviewPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        match action {
+            _ if action.is_soft_quit() => return ComponentAction::Quit,
+            A::Confirm => {
+                self.add();
+                return ComponentAction::Quit;
+            }
+            A::Select => self.toggle_wanted(),
+            A::ChangeFilePriority => self.cycle_priority(),
+            A::Click(column, row) | A::DoubleClick(column, row) => {
+                if !self.tree_state.click_at(Position::new(column, row)) {
+                    return ComponentAction::Nothing;
+                }
+            }
+            A::Left => {
+                self.tree_state.key_left();
+            }
+            A::Right => {
+                self.tree_state.key_right();
+            }
+            A::Up | A::ScrollUpBy(_) => {
+                self.tree_state.key_up();
+            }
+            A::Down | A::ScrollDownBy(_) => {
+                self.tree_state.key_down();
+            }
+            A::Home => {
+                self.tree_state.select_first();
+            }
+            A::End => {
+                self.tree_state.select_last();
+            }
+            _ => return ComponentAction::Nothing,
+        }
+
+        CTX.send_action(A::Render);
+        ComponentAction::Nothing
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let (popup_rect, block_rect, _) = popup_rects(rect, 75, 75);
+
+        let title = format!(" Add {} ", self.pending.metainfo.name);
+        let block = popup_block(&title).title_bottom(Self::keybinding_tip());
+        let inner_rect = block.inner(block_rect);
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(block, block_rect);
+
+        let info_lines = self.info_lines();
+        let info_height = u16::try_from(info_lines.len()).unwrap_or(u16::MAX);
+        let [info_rect, tree_rect] = Layout::vertical([
+            Constraint::Length(info_height.saturating_add(1)),
+            Constraint::Fill(1),
+        ])
+        .areas(inner_rect);
+        f.render_widget(Paragraph::new(info_lines), info_rect);
+
+        if self.tree_state.selected().is_empty() {
+            self.tree_state.select_first();
+        }
+
+        let tree_items = self.tree.make_tree();
+        let tree_widget = Tree::new(&tree_items).unwrap().highlight_style(
+            Style::default()
+                .fg(CONFIG.theme.accent)
+                .bg(CONFIG.theme.highlight_bg)
+                .bold(),
+        );
+        f.render_stateful_widget(tree_widget, tree_rect, &mut self.tree_state);
+    }
+}
@@ -0,0 +1,356 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, List, ListItem, ListState},
+};
+use rm_config::{
+    column_layout::ColumnLayout,
+    keymap::{GeneralAction, TorrentsAction},
+    CONFIG,
+};
+use rm_shared::{
+    action::{Action, ErrorMessage, UpdateAction},
+    header::Header,
+    status_task::StatusTask,
+};
+
+use crate::tui::{
+    components::{keybinding_style, popup_block, popup_rects, Component, ComponentAction},
+    ctx::CTX,
+    tabs::torrents::table_manager::TableManager,
+};
+
+const MAX_WIDTH: u16 = 200;
+
+struct Column {
+    header: Header,
+    shown: bool,
+    /// 0 keeps the header's default width.
+    width: u16,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SaveTarget {
+    State,
+    Config,
+}
+
+/// Shows, hides, reorders and resizes columns of the torrents table. Every
+/// change is applied right away, and the layout can be saved for next time.
+pub struct ColumnEditorPopup {
+    columns: Vec<Column>,
+    list_state: ListState,
+    save_target: SaveTarget,
+    changed: bool,
+    list_rect: Rect,
+}
+
+impl ColumnEditorPopup {
+    pub fn new(layout: &ColumnLayout) -> Self {
+        let mut columns: Vec<Column> = layout
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(idx, header)| Column {
+                header: *header,
+                shown: true,
+                width: layout.width(idx).unwrap_or_default(),
+            })
+            .collect();
+
+        let custom_headers = (0..CONFIG.torrents_tab.custom_headers.len()).map(Header::Custom);
+        for header in Header::ALL.into_iter().chain(custom_headers) {
+            if !layout.headers.contains(&header) {
+                columns.push(Column {
+                    header,
+                    shown: false,
+                    width: 0,
+                });
+            }
+        }
+
+        Self {
+            columns,
+            list_state: ListState::default().with_selected(Some(0)),
+            save_target: SaveTarget::State,
+            changed: false,
+            list_rect: Rect::default(),
+        }
+    }
+
+    pub fn layout(&self) -> ColumnLayout {
+        let shown = self.columns.iter().filter(|column| column.shown);
+
+        ColumnLayout {
+            headers: shown.clone().map(|column| column.header).collect(),
+            header_widths: shown.map(|column| column.width).collect(),
+        }
+    }
+
+    /// The layout if it changed since the last call.
+    pub fn take_changed_layout(&mut self) -> Option<ColumnLayout> {
+        if std::mem::take(&mut self.changed) {
+            Some(self.layout())
+        } else {
+            None
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.list_state.selected().unwrap_or_default()
+    }
+
+    fn select_at(&mut self, row: u16) -> bool {
+        // The list is drawn inside a bordered block.
+        let inner = self.list_rect.inner(Margin::new(1, 1));
+        if row < inner.y || row >= inner.bottom() {
+            return false;
+        }
+
+        let idx = self.list_state.offset() + usize::from(row - inner.y);
+        if idx >= self.columns.len() {
+            return false;
+        }
+
+        self.list_state.select(Some(idx));
+        true
+    }
+
+    fn toggle_shown(&mut self) {
+        let idx = self.selected();
+        let shown_count = self.columns.iter().filter(|column| column.shown).count();
+        // Hiding every column would leave nothing to select torrents by.
+        if self.columns[idx].shown && shown_count == 1 {
+            return;
+        }
+
+        self.columns[idx].shown = !self.columns[idx].shown;
+        self.changed = true;
+    }
+
+    fn move_selected(&mut self, forward: bool) {
+        let idx = self.selected();
+        let target = if forward {
+            idx + 1
+        } else {
+            let Some(target) = idx.checked_sub(1) else {
+                return;
+            };
+            target
+        };
+        if target >= self.columns.len() {
+            return;
+        }
+
+        self.columns.swap(idx, target);
+        self.list_state.select(Some(target));
+        self.changed = true;
+    }
+
+    fn resize_selected(&mut self, grow: bool) {
+        let idx = self.selected();
+        let column = &mut self.columns[idx];
+        let width = match column.width {
+            0 => constraint_width(TableManager::default_header_constraint(&column.header)),
+            width => width,
+        };
+
+        column.width = if grow {
+            (width + 1).min(MAX_WIDTH)
+        } else {
+            width.saturating_sub(1).max(1)
+        };
+        column.shown = true;
+        self.changed = true;
+    }
+
+    fn reset_width(&mut self) {
+        let idx = self.selected();
+        self.columns[idx].width = 0;
+        self.changed = true;
+    }
+
+    fn save(&self) {
+        let layout = self.layout();
+        let result = match self.save_target {
+            SaveTarget::State => layout.save_to_state(),
+            SaveTarget::Config => layout.save_to_config(),
+        };
+
+        match result {
+            Ok(path) => CTX.send_update_action(UpdateAction::StatusTaskSetSuccess(
+                StatusTask::new_save(format!("columns to {}", path.display())),
+            )),
+            Err(e) => {
+                let err_msg = ErrorMessage::new(
+                    "Failed to save columns",
+                    "Encountered an error while saving the column layout",
+                    e.into(),
+                );
+                CTX.send_update_action(UpdateAction::Error(Box::new(err_msg)));
+            }
+        }
+    }
+
+    fn column_label(header: &Header) -> &str {
+        match header {
+            Header::Padding => "(padding)",
+            Header::SmallStatus => "(status icon)",
+            Header::CategoryIcon => "(category icon)",
+            header => CONFIG.torrents_tab.header_name(header),
+        }
+    }
+
+    fn keybinding_tip(&self) -> Line<'static> {
+        let mut keys = vec![];
+
+        if CONFIG.general.beginner_mode {
+            for (action, desc) in [
+                (GeneralAction::Select, "show/hide"),
+                (GeneralAction::MoveToColumnLeft, "move up"),
+                (GeneralAction::MoveToColumnRight, "move down"),
+                (GeneralAction::Left, "narrower"),
+                (GeneralAction::Right, "wider"),
+                (GeneralAction::Confirm, "save"),
+            ] {
+                if let Some(key) = CONFIG
+                    .keybindings
+                    .general
+                    .get_keys_for_action_joined(action)
+                {
+                    keys.push(Span::raw(" "));
+                    keys.push(Span::styled(key, keybinding_style()));
+                    keys.push(Span::raw(format!(" - {desc} ")));
+                }
+            }
+
+            if let Some(key) = CONFIG
+                .keybindings
+                .torrents_tab
+                .get_keys_for_action_joined(TorrentsAction::Delete)
+            {
+                keys.push(Span::raw(" "));
+                keys.push(Span::styled(key, keybinding_style()));
+                keys.push(Span::raw(" - default width "));
+            }
+        }
+
+        Line::from(keys)
+    }
+
+    fn save_target_line(&self) -> Line<'static> {
+        let highlight_style = Style::default().fg(CONFIG.theme.accent).bold();
+        let target = |target: SaveTarget, name: &'static str| {
+            if self.save_target == target {
+                Span::styled(format!("[{name}]"), highlight_style)
+            } else {
+                Span::raw(format!(" {name} "))
+            }
+        };
+
+        let mut spans = vec![Span::raw(" Save to: ")];
+        spans.push(target(SaveTarget::State, "UI state"));
+        spans.push(target(SaveTarget::Config, "config.toml"));
+        if let Some(key) = CONFIG
+            .keybindings
+            .general
+            .get_keys_for_action_joined(GeneralAction::SwitchFocus)
+        {
+            spans.push(Span::raw(" ("));
+            spans.push(Span::styled(key, keybinding_style()));
+            spans.push(Span::raw(") "));
+        }
+
+        Line::from(spans).right_aligned()
+    }
+}
+
+impl Component for ColumnEditorPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        match action {
+            _ if action.is_soft_quit() => return ComponentAction::Quit,
+            A::Confirm => {
+                self.save();
+                return ComponentAction::Quit;
+            }
+            A::Up => self.list_state.select_previous(),
+            A::Down => self.list_state.select_next(),
+            A::Home => self.list_state.select_first(),
+            A::End => self.list_state.select_last(),
+            A::Select => self.toggle_shown(),
+            A::MoveToColumnLeft => self.move_selected(false),
+            A::MoveToColumnRight => self.move_selected(true),
+            A::Left => self.resize_selected(false),
+            A::Right => self.resize_selected(true),
+            A::Delete => self.reset_width(),
+            A::ChangeFocus => {
+                self.save_target = match self.save_target {
+                    SaveTarget::State => SaveTarget::Config,
+                    SaveTarget::Config => SaveTarget::State,
+                };
+            }
+            A::Click(_, row) => {
+                if !self.select_at(row) {
+                    return ComponentAction::Nothing;
+                }
+            }
+            A::DoubleClick(_, row) if self.select_at(row) => self.toggle_shown(),
+            _ => return ComponentAction::Nothing,
+        }
+
+        CTX.send_action(A::Render);
+        ComponentAction::Nothing
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let (popup_rect, block_rect, _) = popup_rects(rect, 50, 75);
+        self.list_rect = block_rect;
+        let dim_style = Style::default().fg(CONFIG.theme.dim);
+
+        let name_len = self
+            .columns
+            .iter()
+            .map(|column| Self::column_label(&column.header).chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let items: Vec<ListItem> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mark = if column.shown { "[x]" } else { "[ ]" };
+                let label = Self::column_label(&column.header);
+                let width = match column.width {
+                    0 => "auto".to_string(),
+                    width => width.to_string(),
+                };
+                let item = ListItem::new(format!("{mark} {label:<name_len$}  {width:>4}"));
+                if column.shown {
+                    item
+                } else {
+                    item.style(dim_style)
+                }
+            })
+            .collect();
+
+        let block = popup_block(" Columns ")
+            .title_bottom(self.keybinding_tip())
+            .title_bottom(self.save_target_line());
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().bg(CONFIG.theme.highlight_bg).bold());
+
+        f.render_widget(Clear, popup_rect);
+        f.render_stateful_widget(list, block_rect, &mut self.list_state);
+    }
+}
+
+/// Columns of a flexible width start resizing from their maximum.
+fn constraint_width(constraint: Constraint) -> u16 {
+    match constraint {
+        Constraint::Length(width) | Constraint::Max(width) | Constraint::Min(width) => width,
+        _ => 12,
+    }
+}
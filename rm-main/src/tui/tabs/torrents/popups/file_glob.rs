@@ -0,0 +1,226 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, List, ListState},
+};
+use rm_config::CONFIG;
+use rm_shared::{
+    action::{Action, UpdateAction},
+    glob::Glob,
+};
+use transmission_rpc::types::{Id, TorrentSetArgs};
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        components::{popup_block, Component, ComponentAction, InputManager},
+        ctx::CTX,
+    },
+};
+
+use super::file_rename::render_input_popup;
+
+const CHOICES: [&str; 4] = [
+    "Toggle wanted",
+    "Low priority",
+    "Normal priority",
+    "High priority",
+];
+
+enum Stage {
+    Pattern {
+        input: InputManager,
+        error: Option<String>,
+    },
+    Choice {
+        glob: Glob,
+        matching: Vec<usize>,
+        list_state: ListState,
+    },
+}
+
+/// Toggles wanted status or sets priority of all files matching a glob.
+pub struct GlobPopup {
+    torrent_id: Id,
+    /// Paths of all of the torrent's files.
+    files: Vec<String>,
+    wanted: Vec<bool>,
+    stage: Stage,
+}
+
+impl GlobPopup {
+    pub fn new(torrent_id: Id, files: Vec<String>, wanted: Vec<bool>) -> Self {
+        CTX.send_update_action(UpdateAction::SwitchToInputMode);
+
+        Self {
+            torrent_id,
+            files,
+            wanted,
+            stage: Stage::Pattern {
+                input: InputManager::new(String::from("Glob: ")),
+                error: None,
+            },
+        }
+    }
+
+    fn apply(&self, choice: usize, matching: Vec<usize>) {
+        let args = match choice {
+            0 => {
+                let wanted_no = matching
+                    .iter()
+                    .filter(|idx| self.wanted.get(**idx).copied().unwrap_or(true))
+                    .count();
+
+                if wanted_no * 2 > matching.len() {
+                    TorrentSetArgs::default().files_unwanted(matching)
+                } else {
+                    TorrentSetArgs::default().files_wanted(matching)
+                }
+            }
+            1 => TorrentSetArgs::default().priority_low(matching),
+            2 => TorrentSetArgs::default().priority_normal(matching),
+            3 => TorrentSetArgs::default().priority_high(matching),
+            _ => unreachable!(),
+        };
+
+        CTX.send_torrent_action(TorrentAction::SetArgs(
+            Box::new(args),
+            Some(vec![self.torrent_id.clone()]),
+        ));
+    }
+
+    fn handle_input(&mut self, input: crossterm::event::KeyEvent) -> ComponentAction {
+        let Stage::Pattern {
+            input: input_mgr,
+            error,
+        } = &mut self.stage
+        else {
+            return ComponentAction::Nothing;
+        };
+
+        match input.code {
+            KeyCode::Esc => {
+                CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+                ComponentAction::Quit
+            }
+            KeyCode::Enter => {
+                match input_mgr.text().parse::<Glob>() {
+                    Ok(glob) => {
+                        let matching: Vec<usize> = self
+                            .files
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, path)| glob.is_match(path))
+                            .map(|(idx, _)| idx)
+                            .collect();
+
+                        if matching.is_empty() {
+                            *error = Some(String::from("No files match"));
+                        } else {
+                            self.stage = Stage::Choice {
+                                glob,
+                                matching,
+                                list_state: ListState::default().with_selected(Some(0)),
+                            };
+                            CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+                        }
+                    }
+                    Err(err) => *error = Some(err.to_string()),
+                }
+                CTX.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+            _ => {
+                *error = None;
+                if input_mgr.handle_key(input).is_some() {
+                    CTX.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
+        }
+    }
+}
+
+impl Component for GlobPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        if let A::Input(input) = action {
+            return self.handle_input(input);
+        }
+
+        if let (A::Paste(text), Stage::Pattern { input, error }) = (&action, &mut self.stage) {
+            *error = None;
+            if input.paste(text).is_some() {
+                CTX.send_action(A::Render);
+            }
+            return ComponentAction::Nothing;
+        }
+
+        let Stage::Choice {
+            matching,
+            list_state,
+            ..
+        } = &mut self.stage
+        else {
+            return ComponentAction::Nothing;
+        };
+
+        match action {
+            _ if action.is_soft_quit() => ComponentAction::Quit,
+            A::Up => {
+                list_state.select_previous();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::Down => {
+                list_state.select_next();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::Confirm => {
+                let choice = list_state.selected().unwrap_or_default();
+                let matching = std::mem::take(matching);
+                self.apply(choice, matching);
+                ComponentAction::Quit
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        match &mut self.stage {
+            Stage::Pattern { input, error } => {
+                render_input_popup(f, rect, " Select by glob ", input, error.as_deref());
+            }
+            Stage::Choice {
+                glob,
+                matching,
+                list_state,
+            } => {
+                let [block_rect] = Layout::horizontal([Constraint::Length(30)])
+                    .flex(layout::Flex::Center)
+                    .areas(rect);
+                let [block_rect] = Layout::vertical([Constraint::Length(CHOICES.len() as u16 + 2)])
+                    .flex(layout::Flex::Center)
+                    .areas(block_rect);
+
+                let title = format!(" {} ({} files) ", glob.as_str(), matching.len());
+                let block = popup_block(&title);
+                let list_rect = block_rect.inner(Margin::new(1, 1));
+
+                let list = List::new(CHOICES.map(|choice| Text::raw(choice).centered()))
+                    .highlight_style(
+                        Style::default()
+                            .fg(CONFIG.theme.accent)
+                            .bg(CONFIG.theme.highlight_bg)
+                            .bold(),
+                    );
+
+                f.render_widget(Clear, block_rect);
+                f.render_widget(block, block_rect);
+                f.render_stateful_widget(list, list_rect, list_state);
+            }
+        }
+    }
+}
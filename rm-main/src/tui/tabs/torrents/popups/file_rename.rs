@@ -0,0 +1,452 @@
+use std::collections::HashSet;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, List, ListItem, ListState, Paragraph},
+};
+use regex::Regex;
+use rm_config::{keymap::GeneralAction, CONFIG};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    status_task::StatusTask,
+};
+use transmission_rpc::types::Id;
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        components::{
+            keybinding_style, popup_block, popup_rects, Component, ComponentAction, InputManager,
+        },
+        ctx::CTX,
+    },
+};
+
+/// A single file rename, as shown in the batch rename preview.
+struct PlannedRename {
+    path: String,
+    new_name: String,
+    problem: Option<&'static str>,
+}
+
+impl PlannedRename {
+    fn name(&self) -> &str {
+        file_name(&self.path)
+    }
+}
+
+enum Stage {
+    Rename {
+        path: String,
+        input: InputManager,
+        error: Option<&'static str>,
+    },
+    Pattern {
+        input: InputManager,
+        error: Option<String>,
+    },
+    Replacement {
+        regex: Regex,
+        input: InputManager,
+    },
+    Preview {
+        renames: Vec<PlannedRename>,
+        list_state: ListState,
+    },
+}
+
+/// Renames a file or a directory inside of a torrent, or a batch of files
+/// using a regex.
+pub struct RenamePopup {
+    torrent_id: Id,
+    /// Paths of all of the torrent's files, used for detecting name clashes.
+    files: Vec<String>,
+    /// Path of a file or a directory the batch rename is limited to.
+    scope: String,
+    stage: Stage,
+}
+
+impl RenamePopup {
+    pub fn new(torrent_id: Id, files: Vec<String>, path: String) -> Self {
+        let input =
+            InputManager::new_with_value(String::from("New name: "), file_name(&path).to_string())
+                .history("rename");
+        CTX.send_update_action(UpdateAction::SwitchToInputMode);
+
+        Self {
+            torrent_id,
+            files,
+            scope: path.clone(),
+            stage: Stage::Rename {
+                path,
+                input,
+                error: None,
+            },
+        }
+    }
+
+    pub fn new_batch(torrent_id: Id, files: Vec<String>, scope: String) -> Self {
+        let input = InputManager::new(String::from("Regex: "));
+        CTX.send_update_action(UpdateAction::SwitchToInputMode);
+
+        Self {
+            torrent_id,
+            files,
+            scope,
+            stage: Stage::Pattern { input, error: None },
+        }
+    }
+
+    fn apply(torrent_id: &Id, renames: &[PlannedRename]) {
+        let valid: Vec<&PlannedRename> = renames
+            .iter()
+            .filter(|rename| rename.problem.is_none())
+            .collect();
+
+        if valid.is_empty() {
+            return;
+        }
+
+        let what = match valid.as_slice() {
+            [rename] => rename.name().to_string(),
+            _ => format!("{} files", valid.len()),
+        };
+        CTX.send_update_action(UpdateAction::StatusTaskSet(StatusTask::new_rename(what)));
+
+        for rename in valid {
+            CTX.send_torrent_action(TorrentAction::Rename(
+                torrent_id.clone(),
+                rename.path.clone(),
+                rename.new_name.clone(),
+            ));
+        }
+    }
+
+    fn handle_input(&mut self, input: crossterm::event::KeyEvent) -> ComponentAction {
+        if input.code == KeyCode::Esc {
+            CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+            return ComponentAction::Quit;
+        }
+
+        if input.code != KeyCode::Enter {
+            let input_mgr = match &mut self.stage {
+                Stage::Pattern { input: mgr, error } => {
+                    *error = None;
+                    mgr
+                }
+                Stage::Rename {
+                    input: mgr, error, ..
+                } => {
+                    *error = None;
+                    mgr
+                }
+                Stage::Replacement { input: mgr, .. } => mgr,
+                Stage::Preview { .. } => return ComponentAction::Nothing,
+            };
+
+            if input_mgr.handle_key(input).is_some() {
+                CTX.send_action(Action::Render);
+            }
+            return ComponentAction::Nothing;
+        }
+
+        match &mut self.stage {
+            Stage::Rename { path, input, error } => {
+                let new_name = input.text();
+                if new_name == file_name(path) {
+                    CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+                    return ComponentAction::Quit;
+                }
+
+                let new_path = sibling_path(path, &new_name);
+                let taken = self.files.iter().any(|file| {
+                    !is_in_scope(file, path) && (file == &new_path || is_in_scope(file, &new_path))
+                });
+                *error = name_problem(&new_name).or(taken.then_some("name already taken"));
+                if error.is_some() {
+                    CTX.send_action(Action::Render);
+                    return ComponentAction::Nothing;
+                }
+
+                input.save_history();
+                CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+                Self::apply(
+                    &self.torrent_id,
+                    &[PlannedRename {
+                        path: path.clone(),
+                        new_name,
+                        problem: None,
+                    }],
+                );
+                ComponentAction::Quit
+            }
+            Stage::Pattern { input, error } => {
+                match Regex::new(&input.text()) {
+                    Ok(regex) => {
+                        self.stage = Stage::Replacement {
+                            regex,
+                            input: InputManager::new(String::from("Replace with: ")),
+                        };
+                    }
+                    Err(err) => *error = Some(err.to_string()),
+                }
+                CTX.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+            Stage::Replacement { regex, input } => {
+                let renames = plan(&self.files, &self.scope, regex, &input.text());
+                self.stage = Stage::Preview {
+                    renames,
+                    list_state: ListState::default().with_selected(Some(0)),
+                };
+                CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+                ComponentAction::Nothing
+            }
+            Stage::Preview { .. } => ComponentAction::Nothing,
+        }
+    }
+
+    fn render_input(&mut self, f: &mut Frame, rect: Rect) {
+        match &mut self.stage {
+            Stage::Rename { input, error, .. } => {
+                render_input_popup(f, rect, " Rename ", input, *error);
+            }
+            Stage::Pattern { input, error } => {
+                render_input_popup(f, rect, " Batch rename ", input, error.as_deref());
+            }
+            Stage::Replacement { input, .. } => {
+                render_input_popup(f, rect, " Batch rename ", input, None);
+            }
+            Stage::Preview { .. } => (),
+        }
+    }
+}
+
+impl Component for RenamePopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        if let A::Input(input) = action {
+            return self.handle_input(input);
+        }
+
+        if let A::Paste(text) = &action {
+            let input_mgr = match &mut self.stage {
+                Stage::Pattern { input: mgr, error } => {
+                    *error = None;
+                    mgr
+                }
+                Stage::Rename {
+                    input: mgr, error, ..
+                } => {
+                    *error = None;
+                    mgr
+                }
+                Stage::Replacement { input: mgr, .. } => mgr,
+                Stage::Preview { .. } => return ComponentAction::Nothing,
+            };
+            if input_mgr.paste(text).is_some() {
+                CTX.send_action(A::Render);
+            }
+            return ComponentAction::Nothing;
+        }
+
+        let Stage::Preview {
+            renames,
+            list_state,
+        } = &mut self.stage
+        else {
+            return ComponentAction::Nothing;
+        };
+
+        match action {
+            _ if action.is_soft_quit() => ComponentAction::Quit,
+            A::Confirm => {
+                Self::apply(&self.torrent_id, renames);
+                ComponentAction::Quit
+            }
+            A::Up | A::ScrollUpBy(_) => {
+                list_state.select_previous();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::Down | A::ScrollDownBy(_) => {
+                list_state.select_next();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let Stage::Preview {
+            renames,
+            list_state,
+        } = &mut self.stage
+        else {
+            self.render_input(f, rect);
+            return;
+        };
+
+        let (popup_rect, block_rect, _) = popup_rects(rect, 75, 50);
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+
+        let problems = renames
+            .iter()
+            .filter(|rename| rename.problem.is_some())
+            .count();
+        let title = format!(" Rename {} files ", renames.len() - problems);
+        let mut block = popup_block(&title);
+
+        if problems > 0 {
+            block = block.title_top(
+                Line::from(format!(" {problems} will be skipped "))
+                    .fg(CONFIG.theme.error)
+                    .right_aligned(),
+            );
+        }
+
+        if let (Some(confirm), Some(close)) = (
+            CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Confirm),
+            CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Close),
+        ) {
+            block = block.title_bottom(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(confirm, keybinding_style()),
+                Span::raw(" - apply | "),
+                Span::styled(close, keybinding_style()),
+                Span::raw(" - cancel "),
+            ]));
+        }
+
+        let list_rect = block_rect.inner(Margin::new(2, 1));
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(block, block_rect);
+
+        if renames.is_empty() {
+            f.render_widget(Paragraph::new("No file names match the regex."), list_rect);
+            return;
+        }
+
+        let items: Vec<ListItem> = renames
+            .iter()
+            .map(|rename| {
+                let mut line = Line::from(vec![
+                    Span::raw(rename.name().to_string()),
+                    Span::raw(" → "),
+                    Span::styled(rename.new_name.clone(), highlight_style),
+                ]);
+                if let Some(problem) = rename.problem {
+                    line.push_span(Span::raw(format!(" ({problem})")).fg(CONFIG.theme.error));
+                }
+                ListItem::new(line)
+            })
+            .collect();
+
+        let list =
+            List::new(items).highlight_style(highlight_style.bg(CONFIG.theme.highlight_bg).bold());
+        f.render_stateful_widget(list, list_rect, list_state);
+    }
+}
+
+/// Renders an input in a small box in the middle of `rect`, with an optional
+/// error below it.
+pub(super) fn render_input_popup(
+    f: &mut Frame,
+    rect: Rect,
+    title: &str,
+    input: &mut InputManager,
+    error: Option<&str>,
+) {
+    let [block_rect] = Layout::horizontal([Constraint::Percentage(60)])
+        .flex(layout::Flex::Center)
+        .areas(rect);
+    let [block_rect] = Layout::vertical([Constraint::Length(3)])
+        .flex(layout::Flex::Center)
+        .areas(block_rect);
+
+    let mut block = popup_block(title);
+    if let Some(error) = error {
+        // Regex errors span multiple lines, the last one says what's wrong.
+        let error = error.lines().last().unwrap_or_default().trim();
+        block = block.title_bottom(Line::from(format!(" {error} ")).fg(CONFIG.theme.error));
+    }
+    let input_rect = block_rect.inner(Margin::new(1, 1));
+
+    f.render_widget(Clear, block_rect);
+    f.render_widget(block, block_rect);
+    input.render(f, input_rect);
+}
+
+fn plan(files: &[String], scope: &str, regex: &Regex, replacement: &str) -> Vec<PlannedRename> {
+    let mut renames: Vec<PlannedRename> = files
+        .iter()
+        .filter(|path| is_in_scope(path, scope))
+        .filter_map(|path| {
+            let name = file_name(path);
+            let new_name = regex.replace_all(name, replacement);
+            (new_name != name).then(|| PlannedRename {
+                path: path.clone(),
+                new_name: new_name.into_owned(),
+                problem: None,
+            })
+        })
+        .collect();
+
+    let renamed: HashSet<&str> = renames.iter().map(|rename| rename.path.as_str()).collect();
+    let mut taken: HashSet<String> = files
+        .iter()
+        .filter(|path| !renamed.contains(path.as_str()))
+        .cloned()
+        .collect();
+
+    for rename in &mut renames {
+        let new_path = sibling_path(&rename.path, &rename.new_name);
+
+        rename.problem = name_problem(&rename.new_name);
+        if rename.problem.is_none() && !taken.insert(new_path) {
+            rename.problem = Some("name already taken");
+        }
+    }
+
+    renames
+}
+
+/// Why the daemon would refuse or misinterpret a new name.
+fn name_problem(new_name: &str) -> Option<&'static str> {
+    if new_name.is_empty() {
+        Some("empty name")
+    } else if new_name.contains('/') {
+        Some("contains \"/\"")
+    } else {
+        None
+    }
+}
+
+/// Path of `path` once it's renamed to `new_name`.
+fn sibling_path(path: &str, new_name: &str) -> String {
+    match path.rsplit_once('/') {
+        Some((dir, _)) => format!("{dir}/{new_name}"),
+        None => new_name.to_string(),
+    }
+}
+
+fn is_in_scope(path: &str, scope: &str) -> bool {
+    path == scope
+        || path
+            .strip_prefix(scope)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
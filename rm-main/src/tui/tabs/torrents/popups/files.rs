@@ -1,5 +1,7 @@
 use std::{collections::BTreeMap, time::Duration};
 
+use crossterm::event::{KeyCode, KeyModifiers};
+
 use ratatui::{
     prelude::*,
     style::Styled,
@@ -13,18 +15,21 @@ use tokio::{sync::oneshot, task::JoinHandle};
 use transmission_rpc::types::{Id, Priority, Torrent, TorrentSetArgs};
 use tui_tree_widget::{Tree, TreeItem, TreeState};
 
+use super::{file_glob::GlobPopup, file_rename::RenamePopup};
 use crate::{
     transmission::TorrentAction,
     tui::{
         components::{
             keybinding_style, popup_block, popup_close_button, popup_close_button_highlight,
-            popup_rects, Component, ComponentAction,
+            popup_close_button_rect, popup_rects, Component, ComponentAction, InputManager,
         },
         ctx::CTX,
+        opener,
     },
 };
 use rm_shared::{
     action::{Action, ErrorMessage, UpdateAction},
+    metainfo::Metainfo,
     status_task::StatusTask,
     utils::{bytes_to_human_format, bytes_to_short_human_format},
 };
@@ -124,8 +129,8 @@ impl Component for PriorityPopup {
         ])
         .highlight_style(
             Style::default()
-                .fg(CONFIG.general.accent_color)
-                .bg(Color::Black)
+                .fg(CONFIG.theme.accent)
+                .bg(CONFIG.theme.highlight_bg)
                 .bold(),
         );
 
@@ -138,11 +143,15 @@ pub struct FilesPopup {
     torrent: Option<Torrent>,
     torrent_id: Id,
     priority_popup: Option<PriorityPopup>,
+    rename_popup: Option<RenamePopup>,
+    glob_popup: Option<GlobPopup>,
+    search: Option<FileSearch>,
     tree_state: TreeState<String>,
     tree: Node,
     current_focus: CurrentFocus,
     switched_after_fetched_data: bool,
     torrent_info_task_handle: JoinHandle<()>,
+    block_rect: Rect,
 }
 
 async fn fetch_new_files(torrent_id: Id) {
@@ -168,6 +177,15 @@ async fn fetch_new_files(torrent_id: Id) {
     }
 }
 
+/// Incremental search through file and directory names.
+struct FileSearch {
+    input: InputManager,
+    /// Selection from before the search, restored when it's cancelled.
+    origin: Vec<String>,
+    /// Position of the current match and the amount of all matches.
+    position: Option<(usize, usize)>,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum CurrentFocus {
     CloseButton,
@@ -191,6 +209,10 @@ impl FilesPopup {
             torrent_id,
             torrent_info_task_handle,
             priority_popup: None,
+            rename_popup: None,
+            glob_popup: None,
+            search: None,
+            block_rect: Rect::default(),
         }
     }
 
@@ -201,6 +223,125 @@ impl FilesPopup {
         }
     }
 
+    /// Path of the highlighted file or directory, relative to the download dir.
+    fn selected_path(&self) -> Option<String> {
+        let torrent = self.torrent.as_ref()?;
+        let mut identifier = self.tree_state.selected().to_vec();
+
+        if let Ok(file_id) = identifier.last()?.parse::<usize>() {
+            identifier.pop();
+            let file = torrent.files.as_ref()?.get(file_id)?;
+            identifier.push(file.name.rsplit('/').next()?.to_string());
+        }
+
+        Some(identifier.join("/"))
+    }
+
+    fn refresh(&mut self) {
+        self.torrent_info_task_handle.abort();
+        self.torrent_info_task_handle =
+            tokio::task::spawn(fetch_new_files(self.torrent_id.clone()));
+    }
+
+    /// Selects a match of the search query, looking from `from` onwards (or
+    /// backwards) and wrapping around.
+    fn select_match(&mut self, from: &[String], forward: bool, skip_current: bool) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+
+        let query = search.input.text().to_lowercase();
+        if query.is_empty() {
+            search.position = None;
+            return;
+        }
+
+        let mut entries = vec![];
+        self.tree.flatten(&[], &mut entries);
+
+        let matches: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, name))| name.to_lowercase().contains(&query))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if matches.is_empty() {
+            search.position = None;
+            return;
+        }
+
+        let current = entries
+            .iter()
+            .position(|(identifier, _)| identifier == from)
+            .unwrap_or_default();
+
+        let nth = if forward {
+            matches
+                .iter()
+                .position(|idx| *idx > current || (*idx == current && !skip_current))
+                .unwrap_or(0)
+        } else {
+            matches
+                .iter()
+                .rposition(|idx| *idx < current || (*idx == current && !skip_current))
+                .unwrap_or(matches.len() - 1)
+        };
+        search.position = Some((nth + 1, matches.len()));
+
+        let identifier = entries.swap_remove(matches[nth]).0;
+        for depth in 1..identifier.len() {
+            self.tree_state.open(identifier[..depth].to_vec());
+        }
+        self.tree_state.select(identifier);
+    }
+
+    fn handle_search_input(&mut self, input: crossterm::event::KeyEvent) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+
+        let ctrl = input.modifiers.contains(KeyModifiers::CONTROL);
+        match (input.code, ctrl) {
+            (KeyCode::Esc, _) => {
+                self.tree_state.select(std::mem::take(&mut search.origin));
+                self.search = None;
+                CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+            }
+            (KeyCode::Enter, _) => {
+                self.search = None;
+                CTX.send_update_action(UpdateAction::SwitchToNormalMode);
+            }
+            (KeyCode::Down, _) | (KeyCode::Char('n'), true) => {
+                let from = self.tree_state.selected().to_vec();
+                self.select_match(&from, true, true);
+            }
+            (KeyCode::Up, _) | (KeyCode::Char('p'), true) => {
+                let from = self.tree_state.selected().to_vec();
+                self.select_match(&from, false, true);
+            }
+            _ => {
+                if search.input.handle_key(input).is_some() {
+                    let origin = search.origin.clone();
+                    self.select_match(&origin, true, false);
+                }
+            }
+        }
+        CTX.send_action(Action::Render);
+    }
+
+    fn handle_search_paste(&mut self, text: &str) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+
+        if search.input.paste(text).is_some() {
+            let origin = search.origin.clone();
+            self.select_match(&origin, true, false);
+            CTX.send_action(Action::Render);
+        }
+    }
+
     fn selected_ids(&self) -> Vec<usize> {
         self.tree_state
             .selected()
@@ -215,6 +356,34 @@ impl Component for FilesPopup {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         use Action as A;
 
+        if self.search.is_some() {
+            match action {
+                A::Input(input) => self.handle_search_input(input),
+                A::Paste(text) => self.handle_search_paste(&text),
+                _ => (),
+            }
+            return ComponentAction::Nothing;
+        }
+
+        if let Some(glob_popup) = &mut self.glob_popup {
+            if glob_popup.handle_actions(action).is_quit() {
+                self.glob_popup = None;
+                self.refresh();
+                CTX.send_action(A::Render);
+            }
+            return ComponentAction::Nothing;
+        }
+
+        if let Some(rename_popup) = &mut self.rename_popup {
+            if rename_popup.handle_actions(action).is_quit() {
+                self.rename_popup = None;
+                // Renames are queued before this, so the fetch will see them.
+                self.refresh();
+                CTX.send_action(A::Render);
+            }
+            return ComponentAction::Nothing;
+        }
+
         match (&mut self.priority_popup, action, self.current_focus) {
             (Some(priority_popup), action, _) => {
                 if priority_popup.handle_actions(action).is_quit() {
@@ -234,6 +403,60 @@ impl Component for FilesPopup {
                 ));
                 CTX.send_action(A::Render);
             }
+            (None, A::Rename, CurrentFocus::Files) => {
+                if let (Some(torrent), Some(path)) = (&self.torrent, self.selected_path()) {
+                    self.rename_popup = Some(RenamePopup::new(
+                        self.torrent_id.clone(),
+                        file_names(torrent),
+                        path,
+                    ));
+                    CTX.send_action(A::Render);
+                }
+            }
+            (None, A::BatchRename, CurrentFocus::Files) => {
+                if let (Some(torrent), Some(scope)) = (&self.torrent, self.selected_path()) {
+                    let files = file_names(torrent);
+                    self.rename_popup = Some(RenamePopup::new_batch(
+                        self.torrent_id.clone(),
+                        files,
+                        scope,
+                    ));
+                    CTX.send_action(A::Render);
+                }
+            }
+            (None, A::Search, CurrentFocus::Files) if self.torrent.is_some() => {
+                self.search = Some(FileSearch {
+                    input: InputManager::new(String::from("Search: ")),
+                    origin: self.tree_state.selected().to_vec(),
+                    position: None,
+                });
+                CTX.send_update_action(UpdateAction::SwitchToInputMode);
+            }
+            (None, A::SelectByGlob, CurrentFocus::Files) => {
+                if let Some(torrent) = &self.torrent {
+                    let files = torrent
+                        .files
+                        .iter()
+                        .flatten()
+                        .map(|file| file.name.clone())
+                        .collect();
+                    let wanted = torrent.wanted.clone().unwrap_or_default();
+                    self.glob_popup = Some(GlobPopup::new(self.torrent_id.clone(), files, wanted));
+                    CTX.send_action(A::Render);
+                }
+            }
+            (None, A::Click(column, row) | A::DoubleClick(column, row), _) => {
+                let position = Position::new(column, row);
+                if popup_close_button_rect(self.block_rect, false).contains(position) {
+                    self.torrent_info_task_handle.abort();
+                    return ComponentAction::Quit;
+                }
+                // Clicking a highlighted directory opens or closes it.
+                if self.tree_state.click_at(position) {
+                    self.current_focus = CurrentFocus::Files;
+                    CTX.send_action(A::Render);
+                }
+            }
             (None, A::ChangeFocus, _) => {
                 self.switch_focus();
                 CTX.send_action(A::Render);
@@ -312,30 +535,18 @@ impl Component for FilesPopup {
                 CTX.send_action(Action::Render);
             }
             (None, A::XdgOpen, CurrentFocus::Files) => {
-                if let Some(torrent) = &self.torrent {
-                    let mut identifier = self.tree_state.selected().to_vec();
-
-                    if identifier.is_empty() {
-                        return ComponentAction::Nothing;
-                    }
-
-                    if let Ok(file_id) = identifier.last().unwrap().parse::<usize>() {
-                        identifier.pop();
-                        identifier
-                            .push(self.tree.get_by_ids(&[file_id]).pop().unwrap().name.clone())
-                    }
-
-                    let sub_path = identifier.join("/");
-
+                if let (Some(torrent), Some(sub_path)) = (&self.torrent, self.selected_path()) {
                     let path = format!("{}/{}", torrent.download_dir.as_ref().unwrap(), sub_path,);
 
-                    match open::that_detached(&path) {
-                        Ok(()) => CTX.send_update_action(UpdateAction::StatusTaskSetSuccess(
-                            StatusTask::new_open(&path),
+                    match opener::open(&path) {
+                        Ok(path) => CTX.send_update_action(UpdateAction::StatusTaskSetSuccess(
+                            StatusTask::new_open(path),
                         )),
                         Err(err) => {
-                            let desc =
-                                format!("An error occured while trying to open \"{}\"", path);
+                            let desc = format!(
+                                "An error occured while trying to open \"{}\"",
+                                CONFIG.connection.local_path(&path)
+                            );
                             let err_msg =
                                 ErrorMessage::new("Failed to open a file", desc, Box::new(err));
                             CTX.send_update_action(UpdateAction::Error(Box::new(err_msg)));
@@ -359,9 +570,10 @@ impl Component for FilesPopup {
 
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         let (popup_rect, block_rect, text_rect) = popup_rects(rect, 75, 75);
+        self.block_rect = block_rect;
 
-        let highlight_style = Style::default().fg(CONFIG.general.accent_color);
-        let bold_highlight_style = highlight_style.on_black().bold();
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+        let bold_highlight_style = highlight_style.bg(CONFIG.theme.highlight_bg).bold();
 
         let block = popup_block(" Files ");
 
@@ -421,7 +633,16 @@ impl Component for FilesPopup {
                         .get_keys_for_action_joined(TorrentsFileViewerAction::ChangeFilePriority)
                     {
                         keys.push(Span::styled(key, keybinding_style()));
-                        keys.push(Span::raw(" - change file priority "));
+                        keys.push(Span::raw(" - change file priority | "));
+                    }
+
+                    if let Some(key) = CONFIG
+                        .keybindings
+                        .torrents_tab_file_viewer
+                        .get_keys_for_action_joined(TorrentsFileViewerAction::Rename)
+                    {
+                        keys.push(Span::styled(key, keybinding_style()));
+                        keys.push(Span::raw(" - rename "));
                     }
 
                     Line::from(keys)
@@ -452,6 +673,34 @@ impl Component for FilesPopup {
             if let Some(popup) = &mut self.priority_popup {
                 popup.render(f, rect);
             }
+
+            if let Some(popup) = &mut self.rename_popup {
+                popup.render(f, rect);
+            }
+
+            if let Some(popup) = &mut self.glob_popup {
+                popup.render(f, rect);
+            }
+
+            if let Some(search) = &mut self.search {
+                let search_rect = Rect {
+                    x: block_rect.x + 1,
+                    y: block_rect.bottom().saturating_sub(2),
+                    width: block_rect.width.saturating_sub(2),
+                    height: 1,
+                };
+                search.input.render(f, search_rect);
+
+                let position = match search.position {
+                    Some((current, total)) => format!("{current}/{total} "),
+                    None if search.input.text().is_empty() => String::new(),
+                    None => String::from("no matches "),
+                };
+                f.render_widget(
+                    Line::from(position).fg(CONFIG.theme.dim).right_aligned(),
+                    search_rect,
+                );
+            }
         } else {
             let paragraph = Paragraph::new("Loading...");
             let block = block.title(popup_close_button_highlight());
@@ -462,7 +711,7 @@ impl Component for FilesPopup {
     }
 }
 
-struct TransmissionFile {
+pub(super) struct TransmissionFile {
     name: String,
     id: usize,
     wanted: bool,
@@ -472,10 +721,14 @@ struct TransmissionFile {
 }
 
 impl TransmissionFile {
-    fn set_wanted(&mut self, new_wanted: bool) {
+    pub(super) fn set_wanted(&mut self, new_wanted: bool) {
         self.wanted = new_wanted;
     }
 
+    pub(super) fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+    }
+
     fn priority_str(&self) -> &'static str {
         match self.priority {
             Priority::Low => "Low",
@@ -485,13 +738,13 @@ impl TransmissionFile {
     }
 }
 
-struct Node {
+pub(super) struct Node {
     items: Vec<TransmissionFile>,
     directories: BTreeMap<String, Node>,
 }
 
 impl Node {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self {
             items: vec![],
             directories: BTreeMap::new(),
@@ -524,6 +777,28 @@ impl Node {
         root
     }
 
+    /// Tree of a torrent that isn't added yet, with all files wanted.
+    pub(super) fn new_from_metainfo(metainfo: &Metainfo) -> Self {
+        let mut root = Self::new();
+
+        for (id, file) in metainfo.files.iter().enumerate() {
+            let path: Vec<String> = file.path.split('/').map(str::to_string).collect();
+
+            let file = TransmissionFile {
+                id,
+                name: path[path.len() - 1].clone(),
+                wanted: true,
+                length: file.length,
+                bytes_completed: 0,
+                priority: Priority::Normal,
+            };
+
+            root.add_transmission_file(file, &path);
+        }
+
+        root
+    }
+
     fn add_transmission_file(&mut self, file: TransmissionFile, remaining_path: &[String]) {
         if let Some((first, rest)) = remaining_path.split_first() {
             if rest.is_empty() {
@@ -539,7 +814,7 @@ impl Node {
         }
     }
 
-    fn get_by_ids(&mut self, ids: &[usize]) -> Vec<&mut TransmissionFile> {
+    pub(super) fn get_by_ids(&mut self, ids: &[usize]) -> Vec<&mut TransmissionFile> {
         let mut transmission_files = vec![];
         for file in &mut self.items {
             if ids.contains(&(file.id as usize)) {
@@ -552,7 +827,45 @@ impl Node {
         transmission_files
     }
 
-    fn make_tree(&self) -> Vec<TreeItem<String>> {
+    /// IDs of all files in the directory at this path, including nested ones.
+    pub(super) fn ids_in(&self, path: &[String]) -> Vec<usize> {
+        let mut node = self;
+        for name in path {
+            let Some(child) = node.directories.get(name) else {
+                return vec![];
+            };
+            node = child;
+        }
+
+        let mut ids = vec![];
+        node.collect_ids(&mut ids);
+        ids
+    }
+
+    fn collect_ids(&self, ids: &mut Vec<usize>) {
+        ids.extend(self.items.iter().map(|file| file.id));
+        for node in self.directories.values() {
+            node.collect_ids(ids);
+        }
+    }
+
+    /// Identifiers and names of all nodes, in the order they are shown in.
+    fn flatten(&self, parent: &[String], entries: &mut Vec<(Vec<String>, String)>) {
+        for transmission_file in &self.items {
+            let mut identifier = parent.to_vec();
+            identifier.push(transmission_file.id.to_string());
+            entries.push((identifier, transmission_file.name.clone()));
+        }
+
+        for (name, node) in &self.directories {
+            let mut identifier = parent.to_vec();
+            identifier.push(name.clone());
+            entries.push((identifier.clone(), name.clone()));
+            node.flatten(&identifier, entries);
+        }
+    }
+
+    pub(super) fn make_tree(&self) -> Vec<TreeItem<String>> {
         let mut tree_items = vec![];
         for transmission_file in &self.items {
             let mut name = Line::default();
@@ -580,13 +893,13 @@ impl Node {
             if progress != 1.0 {
                 name.push_span(Span::styled(
                     progress_percent,
-                    Style::new().fg(CONFIG.general.accent_color),
+                    Style::new().fg(CONFIG.theme.accent),
                 ));
 
                 name.push_span(Span::raw("["));
                 name.push_span(Span::styled(
                     bytes_to_short_human_format(transmission_file.bytes_completed),
-                    Style::new().fg(CONFIG.general.accent_color),
+                    Style::new().fg(CONFIG.theme.accent),
                 ));
                 name.push_span(Span::raw("/"));
                 name.push_span(Span::raw(bytes_to_short_human_format(
@@ -610,3 +923,12 @@ impl Node {
         tree_items
     }
 }
+
+fn file_names(torrent: &Torrent) -> Vec<String> {
+    torrent
+        .files
+        .iter()
+        .flatten()
+        .map(|file| file.name.clone())
+        .collect()
+}
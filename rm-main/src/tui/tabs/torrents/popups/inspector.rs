@@ -0,0 +1,674 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    prelude::*,
+    widgets::{
+        Block, BorderType, Cell, Clear, Paragraph, Row, Sparkline, Table, TableState, Tabs, Wrap,
+    },
+};
+use rm_config::{
+    keymap::{GeneralAction, TorrentsAction},
+    CONFIG,
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    utils::{bytes_to_human_format, seconds_to_human_format},
+};
+use style::Styled;
+use tokio::{sync::oneshot, task::JoinHandle};
+use transmission_rpc::types::{Id, IdleMode, Priority, RatioMode, Torrent, TrackerState};
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        components::{
+            keybinding_style, popup_close_button_highlight, popup_close_button_rect, tab_rects,
+            Component, ComponentAction,
+        },
+        ctx::CTX,
+        tabs::torrents::rustmission_torrent::{RustmissionTorrent, SpeedHistory},
+    },
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InspectorTab {
+    General,
+    Transfer,
+    Files,
+    Peers,
+    Trackers,
+    Speed,
+}
+
+impl InspectorTab {
+    const ALL: [Self; 6] = [
+        Self::General,
+        Self::Transfer,
+        Self::Files,
+        Self::Peers,
+        Self::Trackers,
+        Self::Speed,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::General => "General",
+            Self::Transfer => "Transfer",
+            Self::Files => "Files",
+            Self::Peers => "Peers",
+            Self::Trackers => "Trackers",
+            Self::Speed => "Speed history",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|tab| *tab == self).unwrap()
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+pub struct InspectorPopup {
+    torrent: RustmissionTorrent,
+    details: Option<Torrent>,
+    current_tab: InspectorTab,
+    table_state: TableState,
+    torrent_info_task_handle: JoinHandle<()>,
+    block_rect: Rect,
+    tab_rects: Vec<Rect>,
+}
+
+async fn fetch_details(torrent_id: Id) {
+    loop {
+        let (torrent_tx, torrent_rx) = oneshot::channel();
+        CTX.send_torrent_action(TorrentAction::GetTorrentsById(
+            vec![torrent_id.clone()],
+            torrent_tx,
+        ));
+
+        match torrent_rx.await.unwrap() {
+            Ok(mut torrents) => {
+                if let Some(torrent) = torrents.pop() {
+                    CTX.send_update_action(UpdateAction::UpdateCurrentTorrent(Box::new(torrent)));
+                }
+            }
+            Err(err_message) => {
+                CTX.send_update_action(UpdateAction::Error(err_message));
+            }
+        };
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+impl InspectorPopup {
+    pub fn new(torrent: RustmissionTorrent) -> Self {
+        let torrent_info_task_handle = tokio::task::spawn(fetch_details(torrent.id.clone()));
+
+        Self {
+            torrent,
+            details: None,
+            current_tab: InspectorTab::General,
+            table_state: TableState::default().with_selected(Some(0)),
+            torrent_info_task_handle,
+            block_rect: Rect::default(),
+            tab_rects: vec![],
+        }
+    }
+
+    pub fn torrent_id(&self) -> &Id {
+        &self.torrent.id
+    }
+
+    pub fn set_speed_history(&mut self, speed_history: SpeedHistory) {
+        self.torrent.speed_history = speed_history;
+    }
+
+    fn quit(&self) -> ComponentAction {
+        self.torrent_info_task_handle.abort();
+        ComponentAction::Quit
+    }
+
+    fn forward(&self, action: Action) -> ComponentAction {
+        CTX.send_action(action);
+        self.quit()
+    }
+
+    fn switch_tab(&mut self, tab: InspectorTab) {
+        self.current_tab = tab;
+        self.table_state = TableState::default().with_selected(Some(0));
+        CTX.send_action(Action::Render);
+    }
+
+    fn general_lines(&self) -> Vec<Line<'_>> {
+        let torrent = &self.torrent;
+        let mut lines = vec![Line::from(format!("Name: {}", torrent.torrent_name))];
+
+        if let Some(error) = &torrent.error {
+            lines.push(Line::from(format!("Error: {error}")).fg(CONFIG.theme.error));
+        }
+
+        if let Some(category) = &torrent.category {
+            let mut category_line = Line::from("Category: ");
+            category_line.push_span(Span::styled(
+                category.name.as_str(),
+                Style::default().fg(category.color),
+            ));
+            lines.push(category_line);
+        }
+
+        if !torrent.labels.is_empty() {
+            lines.push(Line::from(format!("Labels: {}", torrent.labels.join(", "))));
+        }
+
+        lines.push(Line::from(format!("Directory: {}", torrent.download_dir)));
+        lines.push(Line::from(format!("Status: {}", torrent.status_str())));
+        lines.push(Line::from(format!(
+            "Size: {} ({} in total)",
+            bytes_to_human_format(torrent.size_when_done),
+            bytes_to_human_format(torrent.total_size)
+        )));
+        lines.push(Line::from(format!("Hash: {}", torrent.hash)));
+        lines.push(Line::from(format!(
+            "Private: {}",
+            if torrent.is_private { "yes" } else { "no" }
+        )));
+
+        if let Some(details) = &self.details {
+            if let (Some(count), Some(size)) = (details.piece_count, details.piece_size) {
+                lines.push(Line::from(format!(
+                    "Pieces: {count} x {}",
+                    bytes_to_human_format(size as i64)
+                )));
+            }
+        }
+
+        lines.push(Line::default());
+
+        lines.push(Line::from(format!(
+            "Added: {}",
+            format_date(Some(torrent.added_date))
+        )));
+        if let Some(details) = &self.details {
+            lines.push(Line::from(format!(
+                "Created: {}",
+                format_date(details.date_created)
+            )));
+        }
+        lines.push(Line::from(format!(
+            "Completed: {}",
+            format_date(Some(torrent.done_date))
+        )));
+        lines.push(Line::from(format!(
+            "Last activity: {}",
+            format_date(Some(torrent.activity_date))
+        )));
+
+        lines.push(Line::default());
+
+        if !torrent.creator.is_empty() {
+            lines.push(Line::from(format!("Creator: {}", torrent.creator)));
+        }
+        if !torrent.comment.is_empty() {
+            lines.push(Line::from(format!("Comment: {}", torrent.comment)));
+        }
+
+        lines.push(Line::default());
+
+        let keys = [
+            ("Delete: ", vec![TorrentsAction::Delete]),
+            ("Show files: ", vec![TorrentsAction::ShowFiles]),
+            ("Rename: ", vec![TorrentsAction::Rename]),
+            ("Move location: ", vec![TorrentsAction::MoveTorrent]),
+            ("Change category: ", vec![TorrentsAction::ChangeCategory]),
+            (
+                "Add / remove label: ",
+                vec![TorrentsAction::AddLabel, TorrentsAction::RemoveLabel],
+            ),
+        ];
+
+        for (desc, actions) in keys {
+            let keys = actions
+                .into_iter()
+                .map(|action| {
+                    CONFIG
+                        .keybindings
+                        .torrents_tab
+                        .get_keys_for_action_joined(action)
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(" / ");
+
+            let mut line = Line::default();
+            line.push_span(Span::raw(desc));
+            line.push_span(Span::styled(keys, keybinding_style()));
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    fn transfer_lines(&self) -> Vec<Line<'_>> {
+        let torrent = &self.torrent;
+        let mut lines = vec![
+            Line::from(format!("Download speed: {}", torrent.download_speed())),
+            Line::from(format!("Upload speed: {}", torrent.upload_speed())),
+            Line::from(format!("ETA: {}", torrent.eta_secs())),
+            Line::default(),
+            Line::from(format!(
+                "Downloaded: {}",
+                bytes_to_human_format(torrent.downloaded_ever)
+            )),
+            Line::from(format!("Uploaded: {}", torrent.uploaded_ever())),
+            Line::from(format!("Ratio: {}", torrent.upload_ratio())),
+            Line::from(format!(
+                "Seeding for: {}",
+                seconds_to_human_format(torrent.seconds_seeding)
+            )),
+        ];
+
+        let Some(details) = &self.details else {
+            return lines;
+        };
+
+        lines.push(Line::default());
+
+        lines.push(Line::from(format!(
+            "Download limit: {}",
+            speed_limit(details.download_limited, details.download_limit)
+        )));
+        lines.push(Line::from(format!(
+            "Upload limit: {}",
+            speed_limit(details.upload_limited, details.upload_limit)
+        )));
+        lines.push(Line::from(format!(
+            "Honors session limits: {}",
+            if details.honors_session_limits.unwrap_or(true) {
+                "yes"
+            } else {
+                "no"
+            }
+        )));
+        if let Some(priority) = &details.bandwidth_priority {
+            lines.push(Line::from(format!(
+                "Bandwidth priority: {}",
+                priority_str(priority)
+            )));
+        }
+        if let Some(peer_limit) = details.peer_limit {
+            lines.push(Line::from(format!("Peer limit: {peer_limit}")));
+        }
+
+        lines.push(Line::default());
+
+        let ratio_goal = match details.seed_ratio_mode {
+            Some(RatioMode::Single) => {
+                format!("{:.2}", details.seed_ratio_limit.unwrap_or_default())
+            }
+            Some(RatioMode::Unlimited) => "unlimited".to_string(),
+            Some(RatioMode::Global) | None => "global".to_string(),
+        };
+        lines.push(Line::from(format!("Seed ratio goal: {ratio_goal}")));
+
+        let idle_goal = match details.seed_idle_mode {
+            Some(IdleMode::Single) => {
+                seconds_to_human_format(details.seed_idle_limit.unwrap_or_default() as i64 * 60)
+            }
+            Some(IdleMode::Unlimited) => "unlimited".to_string(),
+            Some(IdleMode::Global) | None => "global".to_string(),
+        };
+        lines.push(Line::from(format!(
+            "Stop seeding when idle for: {idle_goal}"
+        )));
+
+        if let Some(corrupt) = details.corrupt_ever {
+            lines.push(Line::default());
+            lines.push(Line::from(format!(
+                "Corrupted: {}",
+                bytes_to_human_format(corrupt as i64)
+            )));
+        }
+
+        lines
+    }
+
+    fn files_table(&self) -> Table<'_> {
+        let details = self.details.as_ref();
+        let files = details.and_then(|t| t.files.as_ref());
+        let wanted = details.and_then(|t| t.wanted.as_ref());
+        let priorities = details.and_then(|t| t.priorities.as_ref());
+
+        let rows = files.into_iter().flatten().enumerate().map(|(idx, file)| {
+            let progress = if file.length == 0 {
+                0.0
+            } else {
+                file.bytes_completed as f64 / file.length as f64
+            };
+            let is_wanted = wanted.and_then(|wanted| wanted.get(idx)).is_none_or(|w| *w);
+            let priority = priorities
+                .and_then(|priorities| priorities.get(idx))
+                .map(priority_str)
+                .unwrap_or_default();
+
+            Row::new([
+                Cell::from(if is_wanted { "󰄲" } else { "" }),
+                Cell::from(format!("{:.0}%", progress * 100.0)),
+                Cell::from(bytes_to_human_format(file.length)),
+                Cell::from(priority),
+                Cell::from(file.name.as_str()),
+            ])
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(2),
+                Constraint::Length(5),
+                Constraint::Length(10),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header_row(["", "Done", "Size", "Prio", "Name"]))
+    }
+
+    fn peers_table(&self) -> Table<'_> {
+        let peers = self.details.as_ref().and_then(|t| t.peers.as_ref());
+
+        let rows = peers.into_iter().flatten().map(|peer| {
+            Row::new([
+                Cell::from(format!("{}:{}", peer.address, peer.port)),
+                Cell::from(peer.client_name.as_str()),
+                Cell::from(format!("{:.0}%", peer.progress * 100.0)),
+                Cell::from(format!(
+                    "{}/s",
+                    bytes_to_human_format(peer.rate_to_client as i64)
+                )),
+                Cell::from(format!(
+                    "{}/s",
+                    bytes_to_human_format(peer.rate_to_peer as i64)
+                )),
+                Cell::from(peer.flag_str.as_str()),
+            ])
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(40),
+                Constraint::Fill(1),
+                Constraint::Length(5),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header_row([
+            "Address", "Client", "Done", "Down", "Up", "Flags",
+        ]))
+    }
+
+    fn trackers_table(&self) -> Table<'_> {
+        let trackers = self.details.as_ref().and_then(|t| t.tracker_stats.as_ref());
+
+        let rows = trackers.into_iter().flatten().map(|tracker| {
+            let last_announce = if tracker.has_announced {
+                tracker.last_announce_result.clone()
+            } else {
+                String::new()
+            };
+
+            let next_announce = match tracker.announce_state {
+                TrackerState::Active => "announcing".to_string(),
+                TrackerState::Inactive => String::new(),
+                TrackerState::Waiting | TrackerState::Queued => {
+                    format_date(Some(tracker.next_announce_time))
+                }
+            };
+
+            Row::new([
+                Cell::from(tracker.tier.to_string()),
+                Cell::from(tracker.host.as_str()),
+                Cell::from(tracker.seeder_count.max(0).to_string()),
+                Cell::from(tracker.leecher_count.max(0).to_string()),
+                Cell::from(tracker.download_count.max(0).to_string()),
+                Cell::from(last_announce),
+                Cell::from(next_announce),
+            ])
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Fill(1),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Fill(1),
+                Constraint::Length(19),
+            ],
+        )
+        .header(header_row([
+            "Tier",
+            "Host",
+            "Seeds",
+            "Leeches",
+            "Finished",
+            "Last announce",
+            "Next announce",
+        ]))
+    }
+
+    fn render_speed_history(&self, f: &mut Frame, rect: Rect) {
+        let [download_rect, upload_rect] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(rect);
+
+        let history = &self.torrent.speed_history;
+        for (name, samples, rect) in [
+            ("Download", &history.download, download_rect),
+            ("Upload", &history.upload, upload_rect),
+        ] {
+            // Show the most recent samples that fit.
+            let visible: Vec<u64> = samples
+                .iter()
+                .skip(samples.len().saturating_sub(usize::from(rect.width)))
+                .copied()
+                .collect();
+            let max = visible.iter().max().copied().unwrap_or_default();
+            let avg = visible.iter().sum::<u64>() / visible.len().max(1) as u64;
+
+            let sparkline = Sparkline::default()
+                .block(Block::new().title(format!(
+                    "{name} (avg {}/s - max {}/s)",
+                    bytes_to_human_format(avg as i64),
+                    bytes_to_human_format(max as i64)
+                )))
+                .style(Style::new().fg(CONFIG.theme.accent))
+                .data(&visible);
+
+            f.render_widget(sparkline, rect);
+        }
+    }
+}
+
+impl Component for InspectorPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        match action {
+            _ if action.is_soft_quit() => self.quit(),
+            A::Confirm => self.quit(),
+            A::Left => {
+                self.switch_tab(self.current_tab.previous());
+                ComponentAction::Nothing
+            }
+            A::Right | A::ChangeFocus => {
+                self.switch_tab(self.current_tab.next());
+                ComponentAction::Nothing
+            }
+            A::Click(column, row) => {
+                if popup_close_button_rect(self.block_rect, false)
+                    .contains(Position::new(column, row))
+                {
+                    return self.quit();
+                }
+                let position = Position::new(column, row);
+                if let Some(idx) = self
+                    .tab_rects
+                    .iter()
+                    .position(|rect| rect.contains(position))
+                {
+                    self.switch_tab(InspectorTab::ALL[idx]);
+                }
+                ComponentAction::Nothing
+            }
+            A::Up | A::ScrollUpBy(_) => {
+                self.table_state.select_previous();
+                CTX.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+            A::Down | A::ScrollDownBy(_) => {
+                self.table_state.select_next();
+                CTX.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+            A::Delete
+            | A::ShowFiles
+            | A::Rename
+            | A::ChangeCategory
+            | A::AddLabel
+            | A::RemoveLabel
+            | A::MoveTorrent => self.forward(action),
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn handle_update_action(&mut self, action: UpdateAction) {
+        if let UpdateAction::UpdateCurrentTorrent(torrent) = action {
+            // History comes from the torrents table, see `set_speed_history`.
+            let speed_history = std::mem::take(&mut self.torrent.speed_history);
+            self.torrent = RustmissionTorrent::from((*torrent).clone());
+            self.torrent.speed_history = speed_history;
+            self.details = Some(*torrent);
+            CTX.send_action(Action::Render);
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let title_style = Style::default().fg(CONFIG.theme.accent);
+
+        let mut tab_keys = Line::default();
+        if let (Some(left), Some(right)) = (
+            CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Left),
+            CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Right),
+        ) {
+            tab_keys.push_span(Span::raw(" "));
+            tab_keys.push_span(Span::styled(
+                format!("{left} / {right}"),
+                keybinding_style(),
+            ));
+            tab_keys.push_span(Span::raw(" - switch tab "));
+        }
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CONFIG.theme.border))
+            .title(format!(" {} ", self.torrent.torrent_name).set_style(title_style))
+            .title_bottom(tab_keys.left_aligned())
+            .title_bottom(popup_close_button_highlight());
+
+        let inner_rect = block.inner(rect).inner(Margin::new(1, 0));
+        let [tabs_rect, _, content_rect] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner_rect);
+        self.block_rect = rect;
+
+        let tabs = Tabs::new(InspectorTab::ALL.map(InspectorTab::title))
+            .select(self.current_tab.index())
+            .highlight_style(title_style.bold());
+
+        f.render_widget(Clear, rect);
+        f.render_widget(block, rect);
+        f.render_widget(tabs, tabs_rect);
+        self.tab_rects = tab_rects(tabs_rect, InspectorTab::ALL.map(InspectorTab::title));
+
+        if self.details.is_none() && self.current_tab != InspectorTab::General {
+            f.render_widget(Paragraph::new("Loading..."), content_rect);
+            return;
+        }
+
+        let highlight_style = Style::default().fg(CONFIG.theme.accent).bold();
+
+        match self.current_tab {
+            InspectorTab::General => {
+                let paragraph = Paragraph::new(self.general_lines()).wrap(Wrap { trim: false });
+                f.render_widget(paragraph, content_rect);
+            }
+            InspectorTab::Transfer => {
+                let paragraph = Paragraph::new(self.transfer_lines()).wrap(Wrap { trim: false });
+                f.render_widget(paragraph, content_rect);
+            }
+            InspectorTab::Files | InspectorTab::Peers | InspectorTab::Trackers => {
+                // Tables borrow from self, so the state is moved out for rendering.
+                let mut table_state = std::mem::take(&mut self.table_state);
+                let table = match self.current_tab {
+                    InspectorTab::Files => self.files_table(),
+                    InspectorTab::Peers => self.peers_table(),
+                    _ => self.trackers_table(),
+                }
+                .row_highlight_style(highlight_style);
+
+                f.render_stateful_widget(table, content_rect, &mut table_state);
+                self.table_state = table_state;
+            }
+            InspectorTab::Speed => self.render_speed_history(f, content_rect),
+        }
+    }
+}
+
+fn header_row<const N: usize>(names: [&'static str; N]) -> Row<'static> {
+    Row::new(names).style(Style::default().fg(CONFIG.theme.accent))
+}
+
+fn format_date(date: Option<DateTime<Utc>>) -> String {
+    match date {
+        Some(date) if date.timestamp() > 0 => date
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        _ => "never".to_string(),
+    }
+}
+
+fn speed_limit(limited: Option<bool>, limit: Option<u64>) -> String {
+    match (limited, limit) {
+        (Some(true), Some(limit)) => format!("{limit} kB/s"),
+        _ => "unlimited".to_string(),
+    }
+}
+
+fn priority_str(priority: &Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Normal => "Normal",
+        Priority::High => "High",
+    }
+}
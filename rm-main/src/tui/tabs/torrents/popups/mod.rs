@@ -3,8 +3,11 @@ use crate::tui::{
     ctx::CTX,
 };
 
-use self::{files::FilesPopup, stats::StatisticsPopup};
-use details::DetailsPopup;
+use self::{
+    add_preview::AddPreviewPopup, columns::ColumnEditorPopup, files::FilesPopup,
+    stats::StatisticsPopup, torrent_picker::TorrentPickerPopup,
+};
+use inspector::InspectorPopup;
 use rm_shared::{
     action::{Action, UpdateAction},
     current_window::TorrentWindow,
@@ -12,9 +15,14 @@ use rm_shared::{
 
 use ratatui::prelude::*;
 
-pub mod details;
+pub mod add_preview;
+pub mod columns;
+pub mod file_glob;
+pub mod file_rename;
 pub mod files;
+pub mod inspector;
 pub mod stats;
+pub mod torrent_picker;
 
 pub struct PopupManager {
     pub current_popup: Option<CurrentPopup>,
@@ -23,7 +31,10 @@ pub struct PopupManager {
 pub enum CurrentPopup {
     Stats(StatisticsPopup),
     Files(FilesPopup),
-    Details(DetailsPopup),
+    Inspector(InspectorPopup),
+    TorrentPicker(TorrentPickerPopup),
+    AddPreview(AddPreviewPopup),
+    ColumnEditor(ColumnEditorPopup),
 }
 
 impl PopupManager {
@@ -52,7 +63,10 @@ impl Component for PopupManager {
             let should_close = match current_popup {
                 CurrentPopup::Stats(popup) => popup.handle_actions(action).is_quit(),
                 CurrentPopup::Files(popup) => popup.handle_actions(action).is_quit(),
-                CurrentPopup::Details(popup) => popup.handle_actions(action).is_quit(),
+                CurrentPopup::Inspector(popup) => popup.handle_actions(action).is_quit(),
+                CurrentPopup::TorrentPicker(popup) => popup.handle_actions(action).is_quit(),
+                CurrentPopup::AddPreview(popup) => popup.handle_actions(action).is_quit(),
+                CurrentPopup::ColumnEditor(popup) => popup.handle_actions(action).is_quit(),
             };
 
             if should_close {
@@ -64,8 +78,10 @@ impl Component for PopupManager {
     }
 
     fn handle_update_action(&mut self, action: UpdateAction) {
-        if let Some(CurrentPopup::Files(popup)) = &mut self.current_popup {
-            popup.handle_update_action(action);
+        match &mut self.current_popup {
+            Some(CurrentPopup::Files(popup)) => popup.handle_update_action(action),
+            Some(CurrentPopup::Inspector(popup)) => popup.handle_update_action(action),
+            _ => (),
         }
     }
 
@@ -78,7 +94,10 @@ impl Component for PopupManager {
                 CurrentPopup::Files(popup) => {
                     popup.render(f, rect);
                 }
-                CurrentPopup::Details(popup) => popup.render(f, rect),
+                CurrentPopup::Inspector(popup) => popup.render(f, rect),
+                CurrentPopup::TorrentPicker(popup) => popup.render(f, rect),
+                CurrentPopup::AddPreview(popup) => popup.render(f, rect),
+                CurrentPopup::ColumnEditor(popup) => popup.render(f, rect),
             }
         }
     }
@@ -1,51 +1,205 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Local, TimeZone, Utc};
 use ratatui::{
     prelude::*,
-    widgets::{BarChart, Block, Clear, Paragraph},
+    widgets::{Bar, BarChart, BarGroup, Block, Clear, Paragraph, Tabs},
 };
-use rm_config::CONFIG;
+use rm_config::{keymap::GeneralAction, CONFIG};
 use transmission_rpc::types::SessionStats;
 
-use rm_shared::{action::Action, utils::bytes_to_human_format};
+use rm_shared::{
+    action::{Action, ErrorMessage, UpdateAction},
+    utils::{bytes_to_human_format, bytes_to_short_human_format},
+};
 
-use crate::tui::components::{
-    popup_block_with_close_highlight, popup_rects, Component, ComponentAction,
+use crate::{
+    transmission::stats_history::{self, StatsSample, SAMPLE_INTERVAL},
+    tui::{
+        components::{
+            keybinding_style, popup_block_with_close_highlight, popup_close_button_rect,
+            popup_rects, tab_rects, Component, ComponentAction,
+        },
+        ctx::CTX,
+    },
 };
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StatsRange {
+    /// Speeds since the popup was opened, one bar per refresh.
+    Live,
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsRange {
+    const ALL: [Self; 5] = [Self::Live, Self::Hour, Self::Day, Self::Week, Self::Month];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Live => "Live",
+            Self::Hour => "1h",
+            Self::Day => "24h",
+            Self::Week => "7d",
+            Self::Month => "30d",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|range| *range == self).unwrap()
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// How many bars are shown and how long each of them is, in seconds.
+    fn buckets(self) -> (usize, i64) {
+        const HOUR: i64 = 60 * 60;
+        const DAY: i64 = 24 * HOUR;
+
+        match self {
+            Self::Live => (0, SAMPLE_INTERVAL),
+            Self::Hour => (60, SAMPLE_INTERVAL),
+            Self::Day => (24, HOUR),
+            Self::Week => (7, DAY),
+            Self::Month => (30, DAY),
+        }
+    }
+
+    fn bucket_label(self, start: i64) -> String {
+        let start = Local.timestamp_opt(start, 0).unwrap();
+
+        match self {
+            Self::Live | Self::Hour => String::new(),
+            Self::Day => start.format("%H").to_string(),
+            Self::Week | Self::Month => start.format("%d").to_string(),
+        }
+    }
+
+    /// Start of the bucket a timestamp belongs to. Days start at local midnight.
+    fn bucket_start(self, timestamp: i64) -> i64 {
+        let (_, bucket_len) = self.buckets();
+
+        match self {
+            Self::Live | Self::Hour | Self::Day => timestamp - timestamp.rem_euclid(bucket_len),
+            Self::Week | Self::Month => {
+                let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
+                    .unwrap_or_default()
+                    .with_timezone(&Local)
+                    .date_naive();
+                Local
+                    .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+                    .earliest()
+                    .map_or(timestamp, |midnight| midnight.timestamp())
+            }
+        }
+    }
+}
+
+struct Bucket {
+    start: i64,
+    downloaded: u64,
+    uploaded: u64,
+}
+
 pub struct StatisticsPopup {
     stats: Arc<SessionStats>,
-    upload_data: Vec<(&'static str, u64)>,
-    download_data: Vec<(&'static str, u64)>,
-    max_up: i64,
-    max_down: i64,
+    range: StatsRange,
+    /// Download and upload speeds since the popup was opened, newest first.
+    live_speeds: Vec<(u64, u64)>,
+    samples: Vec<StatsSample>,
+    /// Sample of the current interval, not written to the history yet.
+    in_progress: Option<StatsSample>,
+    block_rect: Rect,
+    tab_rects: Vec<Rect>,
 }
 
 impl StatisticsPopup {
     pub fn new(stats: Arc<SessionStats>) -> Self {
         Self {
-            upload_data: vec![("", stats.upload_speed as u64)],
-            download_data: vec![("", stats.download_speed as u64)],
-            max_up: stats.upload_speed,
-            max_down: stats.download_speed,
+            live_speeds: vec![speeds(&stats)],
             stats,
+            range: StatsRange::Live,
+            samples: vec![],
+            in_progress: stats_history::in_progress(),
+            block_rect: Rect::default(),
+            tab_rects: vec![],
         }
     }
 
-    pub fn update_stats(&mut self, stats: &SessionStats) {
-        let up = stats.upload_speed;
-        let down = stats.download_speed;
+    pub fn update_stats(&mut self, stats: Arc<SessionStats>) {
+        self.live_speeds.insert(0, speeds(&stats));
+        self.stats = stats;
 
-        if up > self.max_up {
-            self.max_up = up;
+        // The previous interval has just been written.
+        let in_progress = stats_history::in_progress();
+        let interval_changed = in_progress.map(|sample| sample.timestamp)
+            != self.in_progress.map(|sample| sample.timestamp);
+        self.in_progress = in_progress;
+        if interval_changed {
+            self.load_samples();
         }
+    }
 
-        if down > self.max_down {
-            self.max_down = down;
+    fn load_samples(&mut self) {
+        if self.range == StatsRange::Live {
+            return;
         }
 
-        self.upload_data.insert(0, ("", up as u64));
-        self.download_data.insert(0, ("", down as u64));
+        let (count, bucket_len) = self.range.buckets();
+        let now = Utc::now().timestamp();
+        let since = self
+            .range
+            .bucket_start(now - (count as i64 - 1) * bucket_len);
+
+        match stats_history::load(since) {
+            Ok(samples) => self.samples = samples,
+            Err(err) => {
+                let err_msg = ErrorMessage::new(
+                    "Failed to load statistics",
+                    "Encountered an error while reading transfer statistics history",
+                    Box::new(err),
+                );
+                CTX.send_update_action(UpdateAction::Error(Box::new(err_msg)));
+            }
+        }
+    }
+
+    fn switch_range(&mut self, range: StatsRange) {
+        self.range = range;
+        self.load_samples();
+        CTX.send_action(Action::Render);
+    }
+
+    fn buckets(&self) -> Vec<Bucket> {
+        let (count, bucket_len) = self.range.buckets();
+        let now = Utc::now().timestamp();
+
+        let mut buckets: Vec<Bucket> = (0..count)
+            .rev()
+            .map(|ago| Bucket {
+                start: self.range.bucket_start(now - ago as i64 * bucket_len),
+                downloaded: 0,
+                uploaded: 0,
+            })
+            .collect();
+
+        for sample in self.samples.iter().chain(&self.in_progress) {
+            let start = self.range.bucket_start(sample.timestamp);
+            if let Some(bucket) = buckets.iter_mut().find(|bucket| bucket.start == start) {
+                bucket.downloaded += sample.downloaded;
+                bucket.uploaded += sample.uploaded;
+            }
+        }
+
+        buckets
     }
 }
 
@@ -55,6 +209,29 @@ impl Component for StatisticsPopup {
         match action {
             _ if action.is_soft_quit() => ComponentAction::Quit,
             A::Confirm => ComponentAction::Quit,
+            A::Left => {
+                self.switch_range(self.range.previous());
+                ComponentAction::Nothing
+            }
+            A::Right | A::ChangeFocus => {
+                self.switch_range(self.range.next());
+                ComponentAction::Nothing
+            }
+            A::Click(column, row) => {
+                let position = Position::new(column, row);
+                if popup_close_button_rect(self.block_rect, true).contains(position) {
+                    return ComponentAction::Quit;
+                }
+                let position = Position::new(column, row);
+                if let Some(idx) = self
+                    .tab_rects
+                    .iter()
+                    .position(|rect| rect.contains(position))
+                {
+                    self.switch_range(StatsRange::ALL[idx]);
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
@@ -62,55 +239,177 @@ impl Component for StatisticsPopup {
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         let (popup_rect, block_rect, text_rect) = popup_rects(rect, 75, 50);
 
-        let [text_rect, _, upload_rect, download_rect] = Layout::vertical([
-            Constraint::Length(3),
+        let [tabs_rect, _, text_rect, _, download_rect, upload_rect] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(4),
             Constraint::Length(1),
             Constraint::Percentage(50),
             Constraint::Percentage(50),
         ])
         .areas(text_rect);
+        self.block_rect = block_rect;
 
-        let block = popup_block_with_close_highlight(" Statistics ");
+        let mut block = popup_block_with_close_highlight(" Statistics ");
+        if let (Some(left), Some(right)) = (
+            CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Left),
+            CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Right),
+        ) {
+            block = block.title_bottom(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(format!("{left} / {right}"), keybinding_style()),
+                Span::raw(" - change range "),
+            ]));
+        }
 
-        let upload_barchart = make_barchart("Upload", self.max_up as u64, &self.upload_data);
-        let download_barchart =
-            make_barchart("Download", self.max_down as u64, &self.download_data);
+        let tabs = Tabs::new(StatsRange::ALL.map(StatsRange::title))
+            .select(self.range.index())
+            .highlight_style(Style::default().fg(CONFIG.theme.accent).bold());
 
         let uploaded_bytes = self.stats.cumulative_stats.uploaded_bytes;
         let downloaded_bytes = self.stats.cumulative_stats.downloaded_bytes;
-        let uploaded = bytes_to_human_format(uploaded_bytes);
-        let downloaded = bytes_to_human_format(downloaded_bytes);
         let ratio = uploaded_bytes as f64 / downloaded_bytes as f64;
-        let text = format!(
-            "Total uploaded: {uploaded}\nTotal downloaded: {downloaded}\nRatio: {ratio:.2}"
+        let totals = format!(
+            "Total downloaded: {} | Total uploaded: {} | Ratio: {ratio:.2}\nCurrent speed: {}/s down | {}/s up",
+            bytes_to_human_format(downloaded_bytes),
+            bytes_to_human_format(uploaded_bytes),
+            bytes_to_human_format(self.stats.download_speed),
+            bytes_to_human_format(self.stats.upload_speed),
         );
+
+        let (text, download_barchart, upload_barchart) = if self.range == StatsRange::Live {
+            (
+                totals,
+                make_live_barchart("Download", &self.live_speeds, |(down, _)| *down),
+                make_live_barchart("Upload", &self.live_speeds, |(_, up)| *up),
+            )
+        } else {
+            let buckets = self.buckets();
+            let downloaded: u64 = buckets.iter().map(|bucket| bucket.downloaded).sum();
+            let uploaded: u64 = buckets.iter().map(|bucket| bucket.uploaded).sum();
+            let samples = || self.samples.iter().chain(&self.in_progress);
+            let peak_download = samples()
+                .map(|sample| sample.peak_download_speed)
+                .max()
+                .unwrap_or_default();
+            let peak_upload = samples()
+                .map(|sample| sample.peak_upload_speed)
+                .max()
+                .unwrap_or_default();
+
+            let text = format!(
+                "Downloaded: {} (peak {}/s)\nUploaded: {} (peak {}/s)\n{totals}",
+                bytes_to_human_format(downloaded as i64),
+                bytes_to_human_format(peak_download as i64),
+                bytes_to_human_format(uploaded as i64),
+                bytes_to_human_format(peak_upload as i64),
+            );
+
+            (
+                text,
+                make_barchart(
+                    "Download",
+                    self.range,
+                    &buckets,
+                    |bucket| bucket.downloaded,
+                    download_rect.width,
+                ),
+                make_barchart(
+                    "Upload",
+                    self.range,
+                    &buckets,
+                    |bucket| bucket.uploaded,
+                    upload_rect.width,
+                ),
+            )
+        };
         let paragraph = Paragraph::new(text);
 
         f.render_widget(Clear, popup_rect);
         f.render_widget(block, block_rect);
+        f.render_widget(tabs, tabs_rect);
+        self.tab_rects = tab_rects(tabs_rect, StatsRange::ALL.map(StatsRange::title));
         f.render_widget(paragraph, text_rect);
-        f.render_widget(upload_barchart, upload_rect);
         f.render_widget(download_barchart, download_rect);
+        f.render_widget(upload_barchart, upload_rect);
     }
 }
 
-fn make_barchart<'a>(
+fn make_barchart(
+    name: &'static str,
+    range: StatsRange,
+    buckets: &[Bucket],
+    value: impl Fn(&Bucket) -> u64,
+    width: u16,
+) -> BarChart<'static> {
+    let count = u16::try_from(buckets.len()).unwrap_or(u16::MAX).max(1);
+    let (bar_width, bar_gap) = if width >= count * 2 {
+        ((width + 1) / count - 1, 1)
+    } else {
+        (1, 0)
+    };
+
+    let bars: Vec<Bar> = buckets
+        .iter()
+        .map(|bucket| {
+            let bytes = value(bucket);
+            let text_value = if bar_width >= 5 {
+                bytes_to_short_human_format(bytes as i64)
+            } else {
+                String::new()
+            };
+
+            Bar::default()
+                .value(bytes)
+                .text_value(text_value)
+                .label(Line::from(range.bucket_label(bucket.start)))
+        })
+        .collect();
+
+    BarChart::default()
+        .block(Block::new().title(name))
+        .bar_width(bar_width)
+        .bar_gap(bar_gap)
+        .bar_style(Style::new().fg(CONFIG.theme.accent))
+        .data(BarGroup::default().bars(&bars))
+}
+
+fn make_live_barchart(
     name: &'static str,
-    max: u64,
-    data: &'a [(&'static str, u64)],
-) -> BarChart<'a> {
-    let avg = bytes_to_human_format(
-        (data.iter().fold(0, |acc, x| acc + x.1) / u64::try_from(data.len()).unwrap()) as i64,
-    );
+    speeds: &[(u64, u64)],
+    value: impl Fn(&(u64, u64)) -> u64,
+) -> BarChart<'static> {
+    let data: Vec<u64> = speeds.iter().map(value).collect();
+    let max = data.iter().copied().max().unwrap_or_default();
+    let avg = data.iter().sum::<u64>() / u64::try_from(data.len().max(1)).unwrap();
+
+    let bars: Vec<Bar> = data
+        .into_iter()
+        .map(|speed| Bar::default().value(speed))
+        .collect();
 
     BarChart::default()
         .block(Block::new().title(format!(
-            "{name} (avg {avg}/sec - max {})",
+            "{name} (avg {}/sec - max {}/sec)",
+            bytes_to_human_format(avg as i64),
             bytes_to_human_format(max as i64)
         )))
         .bar_width(1)
         .bar_gap(0)
-        .bar_style(Style::new().fg(CONFIG.general.accent_color))
-        .data(data)
+        .bar_style(Style::new().fg(CONFIG.theme.accent))
+        .data(BarGroup::default().bars(&bars))
         .max(max)
 }
+
+fn speeds(stats: &SessionStats) -> (u64, u64) {
+    (
+        u64::try_from(stats.download_speed).unwrap_or(0),
+        u64::try_from(stats.upload_speed).unwrap_or(0),
+    )
+}
@@ -0,0 +1,401 @@
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use base64::Engine;
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{Clear, List, ListItem, ListState, Paragraph},
+};
+use rm_config::{keymap::GeneralAction, CONFIG};
+use rm_shared::{
+    action::{Action, ErrorMessage, UpdateAction},
+    metainfo::{Metainfo, PendingTorrent},
+    status_task::StatusTask,
+};
+use transmission_rpc::types::TorrentAddArgs;
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        components::{keybinding_style, popup_block, popup_rects, Component, ComponentAction},
+        ctx::CTX,
+    },
+};
+
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+/// Browses local directories for .torrent files and uploads the chosen ones
+/// to the daemon, so that they don't have to be reachable from its machine.
+pub struct TorrentPickerPopup {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    /// Files marked for adding, possibly from several directories.
+    marked: BTreeSet<PathBuf>,
+    list_state: ListState,
+    read_error: Option<String>,
+    list_rect: Rect,
+}
+
+impl TorrentPickerPopup {
+    pub fn new() -> Self {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let dir = home
+            .as_ref()
+            .map(|home| home.join("Downloads"))
+            .filter(|dir| dir.is_dir())
+            .or(home)
+            .unwrap_or_else(|| PathBuf::from("/"));
+
+        let mut popup = Self {
+            dir: PathBuf::new(),
+            entries: vec![],
+            marked: BTreeSet::new(),
+            list_state: ListState::default(),
+            read_error: None,
+            list_rect: Rect::default(),
+        };
+        popup.open_dir(dir, None);
+        popup
+    }
+
+    /// Lists `dir`, highlighting the entry called `highlight` if there's one.
+    fn open_dir(&mut self, dir: PathBuf, highlight: Option<&str>) {
+        let mut entries = vec![];
+        self.read_error = None;
+
+        match fs::read_dir(&dir) {
+            Ok(read_dir) => {
+                for entry in read_dir.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if name.starts_with('.') {
+                        continue;
+                    }
+
+                    // Follows symlinks, unlike DirEntry::file_type.
+                    let is_dir = entry.path().is_dir();
+                    if is_dir || is_torrent_file(&name) {
+                        entries.push(Entry { name, is_dir });
+                    }
+                }
+            }
+            Err(e) => self.read_error = Some(e.to_string()),
+        }
+
+        entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        let selected = highlight
+            .and_then(|highlight| entries.iter().position(|entry| entry.name == highlight))
+            .unwrap_or_default();
+
+        self.dir = dir;
+        self.entries = entries;
+        self.list_state = ListState::default().with_selected(Some(selected));
+        CTX.send_action(Action::Render);
+    }
+
+    /// Highlights the entry on this terminal row, returning whether there was one.
+    fn select_at(&mut self, row: u16) -> bool {
+        // The list is drawn inside a bordered block.
+        let inner = self.list_rect.inner(Margin::new(1, 1));
+        if row < inner.y || row >= inner.bottom() {
+            return false;
+        }
+
+        let idx = self.list_state.offset() + usize::from(row - inner.y);
+        if idx >= self.entries.len() {
+            return false;
+        }
+
+        self.list_state.select(Some(idx));
+        CTX.send_action(Action::Render);
+        true
+    }
+
+    fn current_entry(&self) -> Option<&Entry> {
+        self.entries.get(self.list_state.selected()?)
+    }
+
+    fn go_up(&mut self) {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let name = self
+            .dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        self.open_dir(parent, name.as_deref());
+    }
+
+    fn enter(&mut self) -> ComponentAction {
+        let Some(entry) = self.current_entry() else {
+            return ComponentAction::Nothing;
+        };
+
+        if entry.is_dir {
+            let dir = self.dir.join(&entry.name);
+            self.open_dir(dir, None);
+            return ComponentAction::Nothing;
+        }
+
+        if self.marked.is_empty() {
+            let path = self.dir.join(&entry.name);
+            preview(&path);
+        } else {
+            self.add_marked();
+        }
+        ComponentAction::Quit
+    }
+
+    fn toggle_current(&mut self) {
+        let Some(entry) = self.current_entry() else {
+            return;
+        };
+
+        if !entry.is_dir {
+            let path = self.dir.join(&entry.name);
+            if !self.marked.remove(&path) {
+                self.marked.insert(path);
+            }
+        }
+        self.list_state.select_next();
+        CTX.send_action(Action::Render);
+    }
+
+    fn toggle_all(&mut self) {
+        let files: Vec<PathBuf> = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| self.dir.join(&entry.name))
+            .collect();
+
+        if files.iter().all(|path| self.marked.contains(path)) {
+            for path in &files {
+                self.marked.remove(path);
+            }
+        } else {
+            self.marked.extend(files);
+        }
+        CTX.send_action(Action::Render);
+    }
+
+    fn add_marked(&mut self) {
+        let marked = std::mem::take(&mut self.marked);
+
+        let what = match marked.len() {
+            0 => return,
+            1 => file_name(marked.first().expect("one file is marked")),
+            len => format!("{len} torrents"),
+        };
+        CTX.send_update_action(UpdateAction::StatusTaskSet(StatusTask::new_add(what)));
+
+        let mut torrents = vec![];
+        for path in marked {
+            match fs::read(&path) {
+                Ok(contents) => {
+                    let args = TorrentAddArgs {
+                        metainfo: Some(base64::engine::general_purpose::STANDARD.encode(contents)),
+                        ..Default::default()
+                    };
+                    torrents.push((file_name(&path), Box::new(args)));
+                }
+                Err(e) => {
+                    let desc = format!("Failed to read \"{}\"", path.display());
+                    let err_msg = ErrorMessage::new("Failed to add a torrent", desc, Box::new(e));
+                    CTX.send_update_action(UpdateAction::Error(Box::new(err_msg)));
+                }
+            }
+        }
+
+        if torrents.is_empty() {
+            CTX.send_update_action(UpdateAction::StatusTaskFailure);
+        } else {
+            CTX.send_torrent_action(TorrentAction::AddAllWithArgs(torrents));
+        }
+    }
+
+    fn keybinding_tip(&self) -> Line<'static> {
+        let mut keys = vec![];
+
+        if CONFIG.general.beginner_mode {
+            if let Some(key) = CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Select)
+            {
+                keys.push(Span::raw(" "));
+                keys.push(Span::styled(key, keybinding_style()));
+                keys.push(Span::raw(" - mark | "));
+            }
+
+            if let Some(key) = CONFIG
+                .keybindings
+                .general
+                .get_keys_for_action_joined(GeneralAction::Confirm)
+            {
+                keys.push(Span::styled(key, keybinding_style()));
+                keys.push(Span::raw(" - open / preview "));
+            }
+        }
+
+        if !self.marked.is_empty() {
+            keys.push(Span::raw(format!(" {} marked ", self.marked.len())));
+        }
+
+        Line::from(keys)
+    }
+}
+
+impl Component for TorrentPickerPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+
+        match action {
+            _ if action.is_soft_quit() => ComponentAction::Quit,
+            A::Up => {
+                self.list_state.select_previous();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::Down => {
+                self.list_state.select_next();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::Home => {
+                self.list_state.select_first();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::End => {
+                self.list_state.select_last();
+                CTX.send_action(A::Render);
+                ComponentAction::Nothing
+            }
+            A::Left => {
+                self.go_up();
+                ComponentAction::Nothing
+            }
+            A::Right => {
+                if self.current_entry().is_some_and(|entry| entry.is_dir) {
+                    self.enter();
+                }
+                ComponentAction::Nothing
+            }
+            A::Confirm => self.enter(),
+            A::Select => {
+                self.toggle_current();
+                ComponentAction::Nothing
+            }
+            A::SelectAll => {
+                self.toggle_all();
+                ComponentAction::Nothing
+            }
+            A::Click(_, row) => {
+                self.select_at(row);
+                ComponentAction::Nothing
+            }
+            A::DoubleClick(_, row) if self.select_at(row) => self.enter(),
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let (popup_rect, block_rect, text_rect) = popup_rects(rect, 75, 75);
+        self.list_rect = block_rect;
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+
+        let block = popup_block(" Add .torrent files ")
+            .title_top(
+                format!(" {} ", self.dir.display())
+                    .set_style(highlight_style)
+                    .into_right_aligned_line(),
+            )
+            .title_bottom(self.keybinding_tip());
+
+        f.render_widget(Clear, popup_rect);
+
+        if let Some(error) = &self.read_error {
+            f.render_widget(block, block_rect);
+            f.render_widget(
+                Paragraph::new(error.as_str()).fg(CONFIG.theme.error),
+                text_rect,
+            );
+            return;
+        }
+
+        if self.entries.is_empty() {
+            f.render_widget(block, block_rect);
+            f.render_widget(Paragraph::new("No .torrent files here"), text_rect);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                if entry.is_dir {
+                    ListItem::new(format!("    {}/", entry.name)).style(highlight_style)
+                } else if self.marked.contains(&self.dir.join(&entry.name)) {
+                    ListItem::new(format!("[x] {}", entry.name))
+                } else {
+                    ListItem::new(format!("[ ] {}", entry.name))
+                }
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().bg(CONFIG.theme.highlight_bg).bold());
+
+        f.render_stateful_widget(list, block_rect, &mut self.list_state);
+    }
+}
+
+/// Opens the file's preview, where its files can be picked before adding it.
+fn preview(path: &Path) {
+    match read_pending(path) {
+        Ok(pending) => CTX.send_update_action(UpdateAction::PreviewTorrent(Box::new(pending))),
+        Err(e) => {
+            let desc = format!("Failed to read \"{}\"", path.display());
+            let err_msg = ErrorMessage::new("Failed to add a torrent", desc, e);
+            CTX.send_update_action(UpdateAction::Error(Box::new(err_msg)));
+        }
+    }
+}
+
+/// Reads and parses a local .torrent file.
+pub fn read_pending(path: &Path) -> Result<PendingTorrent, Box<dyn Error + Send + Sync>> {
+    let contents = fs::read(path)?;
+    let metainfo = Metainfo::from_bytes(&contents)?;
+    Ok(PendingTorrent {
+        metainfo,
+        contents,
+        download_dir: None,
+        category: None,
+    })
+}
+
+fn is_torrent_file(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("torrent"))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
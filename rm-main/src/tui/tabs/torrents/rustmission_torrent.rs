@@ -1,15 +1,25 @@
+use std::collections::VecDeque;
+
 use chrono::{DateTime, Datelike, Utc};
 use ratatui::{
     style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Cell, Row},
 };
-use rm_config::{categories::Category, CONFIG};
+use regex::Regex;
+use rm_config::{
+    categories::Category,
+    main_config::{CustomHeader, CustomHeaderFormat, CustomHeaderKind, RowStatus, RowStyle},
+    CONFIG,
+};
 use rm_shared::{
+    expression::{Expression, TorrentField},
     header::Header,
     utils::{bytes_to_human_format, seconds_to_human_format},
 };
-use transmission_rpc::types::{ErrorType, FileStat, Id, Torrent, TorrentStatus};
+use transmission_rpc::types::{ErrorType, FileStat, Id, Torrent, TorrentStatus, TrackerStat};
+
+use crate::transmission::utils::tracker_host;
 
 #[derive(Clone)]
 pub struct RustmissionTorrent {
@@ -18,9 +28,9 @@ pub struct RustmissionTorrent {
     pub progress: f32,
     pub eta_secs: i64,
     pub download_speed: i64,
-    pub upload_speed: String,
-    pub uploaded_ever: String,
-    pub upload_ratio: String,
+    pub upload_speed: i64,
+    pub uploaded_ever: i64,
+    pub upload_ratio: f32,
     status: TorrentStatus,
     style: Style,
     pub id: Id,
@@ -29,22 +39,105 @@ pub struct RustmissionTorrent {
     pub activity_date: DateTime<Utc>,
     pub added_date: DateTime<Utc>,
     pub peers_connected: i64,
-    pub category: Option<CategoryType>,
+    pub category: Option<Category>,
     pub error: Option<String>,
     pub is_selected: bool,
+    pub total_size: i64,
+    pub downloaded_ever: i64,
+    pub done_date: DateTime<Utc>,
+    pub tracker: Option<String>,
+    pub hash: String,
+    pub queue_position: usize,
+    pub is_private: bool,
+    pub comment: String,
+    pub creator: String,
+    pub seconds_seeding: i64,
+    pub availability: f32,
+    pub labels: Vec<String>,
+    pub seeds: PeerCount,
+    pub leeches: PeerCount,
+    pub is_stalled: bool,
+    pub speed_history: SpeedHistory,
 }
 
-#[derive(Clone)]
-pub enum CategoryType {
-    Plain(String),
-    Config(Category),
+// Fits into the default width of history headers.
+const SPARKLINE_WIDTH: usize = 12;
+
+/// How many refreshes worth of rates are remembered per torrent.
+const SPEED_HISTORY_LEN: usize = 256;
+
+/// Download and upload rates from the most recent refreshes, oldest first.
+#[derive(Clone, Default)]
+pub struct SpeedHistory {
+    pub download: VecDeque<u64>,
+    pub upload: VecDeque<u64>,
+}
+
+impl SpeedHistory {
+    fn new(download_speed: i64, upload_speed: i64) -> Self {
+        let mut history = Self::default();
+        history.push(download_speed, upload_speed);
+        history
+    }
+
+    pub fn push(&mut self, download_speed: i64, upload_speed: i64) {
+        for (samples, speed) in [
+            (&mut self.download, download_speed),
+            (&mut self.upload, upload_speed),
+        ] {
+            if samples.len() == SPEED_HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back(u64::try_from(speed).unwrap_or(0));
+        }
+    }
+
+    pub fn mean(samples: &VecDeque<u64>) -> u64 {
+        if samples.is_empty() {
+            0
+        } else {
+            samples.iter().sum::<u64>() / samples.len() as u64
+        }
+    }
+
+    /// Last `width` samples drawn with block characters, scaled to their peak.
+    fn sparkline(samples: &VecDeque<u64>, width: usize) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let recent = samples.iter().skip(samples.len().saturating_sub(width));
+        let max = recent.clone().max().copied().unwrap_or_default();
+
+        recent
+            .map(|sample| {
+                if *sample == 0 {
+                    ' '
+                } else {
+                    let level = (*sample as f64 / max as f64 * BARS.len() as f64).ceil() as usize;
+                    BARS[level.clamp(1, BARS.len()) - 1]
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PeerCount {
+    pub connected: i64,
+    // Biggest count reported by trackers, None if no tracker knows it.
+    pub total: Option<i64>,
+}
+
+impl PeerCount {
+    fn new(connected: i64, total: Option<i64>) -> Self {
+        Self { connected, total }
+    }
 }
 
-impl CategoryType {
-    pub fn name(&self) -> &str {
-        match self {
-            CategoryType::Plain(name) => name,
-            CategoryType::Config(config) => &config.name,
+impl std::fmt::Display for PeerCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.total {
+            Some(total) => write!(f, "{}/{}", self.connected, total),
+            None => write!(f, "{}", self.connected),
         }
     }
 }
@@ -81,10 +174,110 @@ impl RustmissionTorrent {
         }
     }
 
+    pub fn upload_speed(&self) -> String {
+        match self.upload_speed {
+            0 => String::default(),
+            upload => bytes_to_human_format(upload),
+        }
+    }
+
+    pub fn uploaded_ever(&self) -> String {
+        bytes_to_human_format(self.uploaded_ever)
+    }
+
+    pub fn upload_ratio(&self) -> String {
+        format!("{:.1}", self.upload_ratio)
+    }
+
     pub fn size_when_done(&self) -> String {
         bytes_to_human_format(self.size_when_done)
     }
 
+    pub fn status_str(&self) -> &'static str {
+        if self.error.is_some() {
+            return "Error";
+        }
+
+        match self.status {
+            TorrentStatus::Stopped => "Stopped",
+            TorrentStatus::QueuedToVerify => "Queued to verify",
+            TorrentStatus::Verifying => "Verifying",
+            TorrentStatus::QueuedToDownload => "Queued",
+            TorrentStatus::Downloading => "Downloading",
+            TorrentStatus::QueuedToSeed => "Queued to seed",
+            TorrentStatus::Seeding => "Seeding",
+        }
+    }
+
+    pub fn availability(&self) -> String {
+        format!("{:.1}%", self.availability * 100f32)
+    }
+
+    pub fn field_value(&self, field: TorrentField) -> f64 {
+        match field {
+            TorrentField::Id => match self.id {
+                Id::Id(id) => id as f64,
+                Id::Hash(_) => f64::NAN,
+            },
+            TorrentField::SizeWhenDone => self.size_when_done as f64,
+            TorrentField::TotalSize => self.total_size as f64,
+            TorrentField::Progress => f64::from(self.progress),
+            TorrentField::Eta => self.eta_secs as f64,
+            TorrentField::DownloadRate => self.download_speed as f64,
+            TorrentField::UploadRate => self.upload_speed as f64,
+            TorrentField::UploadRatio => f64::from(self.upload_ratio),
+            TorrentField::UploadedEver => self.uploaded_ever as f64,
+            TorrentField::DownloadedEver => self.downloaded_ever as f64,
+            TorrentField::PeersConnected => self.peers_connected as f64,
+            TorrentField::SecondsSeeding => self.seconds_seeding as f64,
+            TorrentField::Availability => f64::from(self.availability),
+            TorrentField::QueuePosition => self.queue_position as f64,
+            TorrentField::AddedDate => self.added_date.timestamp() as f64,
+            TorrentField::ActivityDate => self.activity_date.timestamp() as f64,
+            TorrentField::DoneDate => self.done_date.timestamp() as f64,
+            TorrentField::Now => Utc::now().timestamp() as f64,
+        }
+    }
+
+    pub fn eval(&self, expr: &Expression) -> f64 {
+        expr.eval(&|field| self.field_value(field))
+    }
+
+    pub fn matching_label(&self, regex: &Regex) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|label| regex.is_match(label))
+            .map(String::as_str)
+    }
+
+    fn custom_header_cell(&self, custom_header: &CustomHeader) -> Cell<'_> {
+        let (expr, format) = match &custom_header.kind {
+            CustomHeaderKind::Expression { expr, format } => (expr, format),
+            CustomHeaderKind::Label(regex) => {
+                return Cell::from(self.matching_label(regex).unwrap_or_default())
+            }
+        };
+
+        let value = self.eval(expr);
+        if !value.is_finite() {
+            return Cell::default();
+        }
+
+        match format {
+            CustomHeaderFormat::Number if value.fract() == 0.0 => Cell::from(value.to_string()),
+            CustomHeaderFormat::Number => Cell::from(format!("{value:.2}")),
+            CustomHeaderFormat::Bytes => Cell::from(bytes_to_human_format(value as i64)),
+            CustomHeaderFormat::Speed => {
+                Cell::from(format!("{}/s", bytes_to_human_format(value as i64)))
+            }
+            CustomHeaderFormat::Percent => Cell::from(format!("{:.1}%", value * 100.0)),
+            CustomHeaderFormat::Duration => Cell::from(seconds_to_human_format(value as i64)),
+            CustomHeaderFormat::Date => DateTime::from_timestamp(value as i64, 0)
+                .map(|date| time_to_line(date).into())
+                .unwrap_or_default(),
+        }
+    }
+
     pub fn to_row_with_higlighted_indices(
         &self,
         highlighted_indices: &Vec<usize>,
@@ -188,7 +381,7 @@ impl RustmissionTorrent {
     }
 
     fn category_icon_span(&self) -> Span {
-        if let Some(CategoryType::Config(category)) = &self.category {
+        if let Some(category) = &self.category {
             Span::styled(
                 format!("{} ", category.icon),
                 Style::default().fg(category.color),
@@ -201,7 +394,7 @@ impl RustmissionTorrent {
     fn torrent_name_with_category_icon(&self) -> Line<'_> {
         let mut line = Line::default();
 
-        if let Some(CategoryType::Config(category)) = &self.category {
+        if let Some(category) = &self.category {
             line.push_span(Span::styled(
                 category.icon.as_str(),
                 Style::default().fg(category.color),
@@ -221,7 +414,7 @@ impl RustmissionTorrent {
                         Line::from(self.torrent_name.as_str()),
                         Line::from(Span::styled(
                             format!("\n{error}"),
-                            Style::default().red().dim().italic(),
+                            Style::default().fg(CONFIG.theme.error).dim().italic(),
                         )),
                     ])
                 } else if CONFIG.torrents_tab.category_icon_insert_into_name {
@@ -234,15 +427,15 @@ impl RustmissionTorrent {
             Header::Progress => Cell::from(self.progress()),
             Header::Eta => Cell::from(self.eta_secs()),
             Header::DownloadRate => Cell::from(download_speed_format(&self.download_speed())),
-            Header::UploadRate => Cell::from(upload_speed_format(&self.upload_speed)),
+            Header::UploadRate => Cell::from(upload_speed_format(&self.upload_speed())),
             Header::DownloadDir => Cell::from(self.download_dir.as_str()),
             Header::Padding => Cell::from(""),
             Header::Id => match &self.id {
                 Id::Id(id) => Cell::from(id.to_string()),
                 Id::Hash(hash) => Cell::from(hash.as_str()),
             },
-            Header::UploadRatio => Cell::from(self.upload_ratio.as_str()),
-            Header::UploadedEver => Cell::from(self.uploaded_ever.as_str()),
+            Header::UploadRatio => Cell::from(self.upload_ratio()),
+            Header::UploadedEver => Cell::from(self.uploaded_ever()),
             Header::ActivityDate => time_to_line(self.activity_date).into(),
             Header::AddedDate => time_to_line(self.added_date).into(),
             Header::PeersConnected => Cell::from(self.peers_connected.to_string()),
@@ -259,7 +452,7 @@ impl RustmissionTorrent {
                     TorrentStatus::QueuedToSeed => Cell::from(CONFIG.icons.loading.as_str()),
                     TorrentStatus::Downloading => Cell::from(CONFIG.icons.download.as_str()),
                     TorrentStatus::Seeding => {
-                        if !self.upload_speed.is_empty() {
+                        if self.upload_speed != 0 {
                             Cell::from(CONFIG.icons.upload.as_str())
                         } else {
                             Cell::from(CONFIG.icons.success.as_str())
@@ -269,23 +462,59 @@ impl RustmissionTorrent {
             }
             Header::Category => {
                 if let Some(category) = &self.category {
-                    match category {
-                        CategoryType::Plain(name) => Cell::from(name.as_str()),
-                        CategoryType::Config(category) => {
-                            Cell::from(category.name.as_str()).fg(category.color)
-                        }
-                    }
+                    Cell::from(category.name.as_str()).fg(category.color)
                 } else {
                     Cell::default()
                 }
             }
             Header::CategoryIcon => {
-                if let Some(CategoryType::Config(category)) = &self.category {
+                if let Some(category) = &self.category {
                     Cell::from(category.icon.as_str()).fg(category.color)
                 } else {
                     Cell::default()
                 }
             }
+            Header::Status => Cell::from(self.status_str()),
+            Header::Seeds => Cell::from(self.seeds.to_string()),
+            Header::Leeches => Cell::from(self.leeches.to_string()),
+            Header::TotalSize => Cell::from(bytes_to_human_format(self.total_size)),
+            Header::DownloadedEver => Cell::from(bytes_to_human_format(self.downloaded_ever)),
+            Header::DoneDate => {
+                if self.done_date == DateTime::UNIX_EPOCH {
+                    Cell::default()
+                } else {
+                    time_to_line(self.done_date).into()
+                }
+            }
+            Header::Tracker => Cell::from(self.tracker.as_deref().unwrap_or_default()),
+            Header::InfoHash => Cell::from(self.hash.as_str()),
+            Header::QueuePosition => Cell::from(self.queue_position.to_string()),
+            Header::Private => {
+                if self.is_private {
+                    Cell::from("Yes")
+                } else {
+                    Cell::from("No")
+                }
+            }
+            Header::Comment => Cell::from(self.comment.as_str()),
+            Header::Creator => Cell::from(self.creator.as_str()),
+            Header::SecondsSeeding => match self.seconds_seeding {
+                0 => Cell::default(),
+                seconds => Cell::from(seconds_to_human_format(seconds)),
+            },
+            Header::Availability => Cell::from(self.availability()),
+            Header::Labels => Cell::from(self.labels.join(", ")),
+            Header::DownloadHistory => Cell::from(SpeedHistory::sparkline(
+                &self.speed_history.download,
+                SPARKLINE_WIDTH,
+            )),
+            Header::UploadHistory => Cell::from(SpeedHistory::sparkline(
+                &self.speed_history.upload,
+                SPARKLINE_WIDTH,
+            )),
+            Header::Custom(idx) => {
+                self.custom_header_cell(&CONFIG.torrents_tab.custom_headers[idx])
+            }
         }
     }
 
@@ -294,15 +523,56 @@ impl RustmissionTorrent {
     }
 
     pub fn update_status(&mut self, new_status: TorrentStatus) {
-        if self.error.is_some() {
-            self.style = Style::default().red();
-        } else if new_status == TorrentStatus::Stopped {
-            self.style = Style::default().dark_gray().italic();
-        } else {
-            self.style = Style::default();
-        }
-
         self.status = new_status;
+        self.style = self.row_style();
+    }
+
+    fn row_style(&self) -> Style {
+        CONFIG
+            .torrents_tab
+            .row_styles
+            .iter()
+            .find(|row_style| self.matches_row_style(row_style))
+            .map(RowStyle::style)
+            .unwrap_or_default()
+    }
+
+    fn matches_row_style(&self, row_style: &RowStyle) -> bool {
+        let status = if self.error.is_some() {
+            RowStatus::Error
+        } else {
+            match self.status {
+                TorrentStatus::Stopped => RowStatus::Stopped,
+                TorrentStatus::QueuedToVerify => RowStatus::QueuedToVerify,
+                TorrentStatus::Verifying => RowStatus::Verifying,
+                TorrentStatus::QueuedToDownload => RowStatus::QueuedToDownload,
+                TorrentStatus::Downloading => RowStatus::Downloading,
+                TorrentStatus::QueuedToSeed => RowStatus::QueuedToSeed,
+                TorrentStatus::Seeding => RowStatus::Seeding,
+            }
+        };
+        let age = (Utc::now() - self.added_date).num_seconds();
+
+        (row_style.status.is_empty() || row_style.status.contains(&status))
+            && row_style
+                .min_ratio
+                .is_none_or(|min_ratio| self.upload_ratio >= min_ratio)
+            && row_style
+                .max_ratio
+                .is_none_or(|max_ratio| self.upload_ratio < max_ratio)
+            && row_style.category.as_ref().is_none_or(|category| {
+                self.category
+                    .as_ref()
+                    .is_some_and(|own_category| own_category.name == *category)
+            })
+            && row_style
+                .stalled
+                .is_none_or(|stalled| self.is_stalled == stalled)
+            && row_style
+                .private
+                .is_none_or(|private| self.is_private == private)
+            && row_style.min_age.is_none_or(|min_age| age >= min_age)
+            && row_style.max_age.is_none_or(|max_age| age < max_age)
     }
 }
 
@@ -320,10 +590,7 @@ impl From<Torrent> for RustmissionTorrent {
 
         let download_speed = t.rate_download.expect("field requested");
 
-        let upload_speed = match t.rate_upload.expect("field requested") {
-            0 => String::default(),
-            upload => bytes_to_human_format(upload),
-        };
+        let upload_speed = t.rate_upload.expect("field requested");
 
         let status = t.status.expect("field requested");
 
@@ -331,12 +598,9 @@ impl From<Torrent> for RustmissionTorrent {
 
         let file_stats = t.file_stats.expect("field requested");
 
-        let uploaded_ever = bytes_to_human_format(t.uploaded_ever.expect("field requested"));
+        let uploaded_ever = t.uploaded_ever.expect("field requested");
 
-        let upload_ratio = {
-            let raw = t.upload_ratio.expect("field requested");
-            format!("{:.1}", raw)
-        };
+        let upload_ratio = t.upload_ratio.expect("field requested");
 
         let activity_date = t.activity_date.expect("field requested");
 
@@ -352,27 +616,71 @@ impl From<Torrent> for RustmissionTorrent {
             }
         };
 
-        let style = {
-            if error.is_some() {
-                Style::default().red()
-            } else {
-                match status {
-                    TorrentStatus::Stopped => Style::default().dark_gray().italic(),
-                    _ => Style::default(),
-                }
-            }
-        };
+        let total_size = t.total_size.expect("field requested");
+
+        let downloaded_ever =
+            i64::try_from(t.downloaded_ever.expect("field requested")).unwrap_or(i64::MAX);
+
+        let done_date = t.done_date.expect("field requested");
+
+        let tracker_stats = t.tracker_stats.expect("field requested");
 
-        let category = if let Some(category) = t.labels.unwrap().first() {
-            match CONFIG.categories.map.get(category) {
-                Some(category) => Some(CategoryType::Config(category.clone())),
-                None => Some(CategoryType::Plain(category.to_string())),
+        let tracker = primary_tracker_host(&tracker_stats);
+
+        let hash = t.hash_string.expect("field requested");
+
+        let queue_position = t.queue_position.expect("field requested");
+
+        let is_private = t.is_private.expect("field requested");
+
+        let comment = t.comment.expect("field requested");
+
+        let creator = t.creator.expect("field requested");
+
+        let seconds_seeding = t.seconds_seeding.expect("field requested");
+
+        let availability = {
+            let left_until_done = t.left_until_done.expect("field requested");
+            let desired_available = t.desired_available.expect("field requested");
+            if size_when_done == 0 {
+                0f32
+            } else if left_until_done == 0 {
+                1f32
+            } else {
+                let available = size_when_done - left_until_done
+                    + i64::try_from(desired_available).unwrap_or(0);
+                (available as f64 / size_when_done as f64) as f32
             }
-        } else {
-            None
         };
 
-        Self {
+        let seeds = PeerCount::new(
+            t.peers_sending_to_us.expect("field requested"),
+            tracker_stats
+                .iter()
+                .map(|stat| stat.seeder_count)
+                .filter(|count| *count >= 0)
+                .max(),
+        );
+
+        let leeches = PeerCount::new(
+            t.peers_getting_from_us.expect("field requested"),
+            tracker_stats
+                .iter()
+                .map(|stat| stat.leecher_count)
+                .filter(|count| *count >= 0)
+                .max(),
+        );
+
+        let labels = t.labels.expect("field requested");
+
+        let category = CONFIG
+            .categories
+            .category_label(&labels)
+            .map(|category| CONFIG.categories.map[category].clone());
+
+        let is_stalled = t.is_stalled.expect("field requested");
+
+        let mut torrent = Self {
             torrent_name,
             size_when_done,
             progress,
@@ -380,7 +688,7 @@ impl From<Torrent> for RustmissionTorrent {
             download_speed,
             upload_speed,
             status,
-            style,
+            style: Style::default(),
             id,
             download_dir,
             file_stats,
@@ -392,10 +700,33 @@ impl From<Torrent> for RustmissionTorrent {
             category,
             error,
             is_selected: false,
-        }
+            total_size,
+            downloaded_ever,
+            done_date,
+            tracker,
+            hash,
+            queue_position,
+            is_private,
+            comment,
+            creator,
+            seconds_seeding,
+            availability,
+            labels,
+            seeds,
+            leeches,
+            is_stalled,
+            speed_history: SpeedHistory::new(download_speed, upload_speed),
+        };
+        torrent.style = torrent.row_style();
+        torrent
     }
 }
 
+fn primary_tracker_host(tracker_stats: &[TrackerStat]) -> Option<String> {
+    let primary_tracker = tracker_stats.iter().min_by_key(|stat| stat.tier)?;
+    tracker_host(primary_tracker)
+}
+
 fn time_to_line<'a>(time: DateTime<Utc>) -> Line<'a> {
     let today = chrono::Local::now();
     if time.year() == today.year() && time.month() == today.month() && time.day() == today.day() {
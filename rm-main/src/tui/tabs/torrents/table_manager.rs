@@ -1,13 +1,20 @@
 use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use ratatui::{prelude::*, widgets::Row};
-use rm_config::CONFIG;
-use rm_shared::header::Header;
-use std::{cmp::Ordering, collections::HashMap};
+use rm_config::{
+    column_layout::ColumnLayout,
+    main_config::{CustomHeaderKind, GroupBy},
+    CONFIG,
+};
+use rm_shared::{header::Header, utils::bytes_to_human_format};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap, HashSet},
+};
 use transmission_rpc::types::Id;
 
 use crate::tui::components::GenericTable;
 
-use super::rustmission_torrent::RustmissionTorrent;
+use super::rustmission_torrent::{RustmissionTorrent, SpeedHistory};
 
 pub struct TableManager {
     pub table: GenericTable<RustmissionTorrent>,
@@ -18,18 +25,66 @@ pub struct TableManager {
     pub sort_reverse: bool,
     pub sorting_is_being_selected: bool,
     pub selected_torrents_ids: Vec<i64>,
+    visual_selection: Option<VisualSelection>,
+    layout: ColumnLayout,
+    pub group_by: Option<GroupBy>,
+    groups: Vec<Group>,
+    collapsed_groups: HashSet<String>,
+    /// What the table shows, row by row.
+    listed: Vec<ListedRow>,
+}
+
+/// Torrents sharing a category, directory, tracker or status.
+struct Group {
+    key: String,
+    torrents: Vec<ListedTorrent>,
+    size: i64,
+    download_speed: i64,
+    upload_speed: i64,
+}
+
+#[derive(Clone, Copy)]
+struct ListedTorrent {
+    /// Index into the table's items.
+    item: usize,
+    /// Position among the filter's matches, if there's a filter.
+    filter_pos: Option<usize>,
+}
+
+#[derive(Clone, Copy)]
+enum ListedRow {
+    /// Header of the group at this index.
+    Group(usize),
+    Torrent(ListedTorrent),
+}
+
+/// Range selection started with the visual mode key.
+struct VisualSelection {
+    /// Position of the row the range starts at.
+    anchor: usize,
+    /// Torrents that were selected before entering the visual mode.
+    base: Vec<i64>,
 }
 
 pub struct Filter {
     pub pattern: String,
     indexes: Vec<u16>,
     highlight_indices: Vec<Vec<usize>>,
+    /// Results per torrent, reused until its name or labels change.
+    matches: HashMap<Id, FilterMatch>,
+}
+
+struct FilterMatch {
+    name: String,
+    labels: Vec<String>,
+    highlight_indices: Option<Vec<usize>>,
 }
 
 impl TableManager {
     pub fn new() -> Self {
         let table = GenericTable::new(vec![]);
-        let widths = Self::default_widths(&CONFIG.torrents_tab.headers);
+        let layout = ColumnLayout::load();
+        let widths = Self::default_widths(&layout);
 
         Self {
             table,
@@ -40,6 +95,12 @@ impl TableManager {
             sort_reverse: false,
             sorting_is_being_selected: false,
             selected_torrents_ids: vec![],
+            visual_selection: None,
+            layout,
+            group_by: CONFIG.torrents_tab.group_by,
+            groups: vec![],
+            collapsed_groups: HashSet::new(),
+            listed: vec![],
         }
     }
 
@@ -66,6 +127,18 @@ impl TableManager {
         self.sorting_is_being_selected = false;
     }
 
+    /// Sorts by the given column, reversing the order if it's already sorted by it.
+    pub fn sort_by_column(&mut self, column: usize) {
+        if self.sort_header == Some(column) && !self.sorting_is_being_selected {
+            self.sort_reverse = !self.sort_reverse;
+        } else {
+            self.sort_header = Some(column);
+            self.sort_reverse = false;
+        }
+        self.sorting_is_being_selected = false;
+        self.sort();
+    }
+
     pub fn move_to_column_left(&mut self) {
         if let Some(selected) = self.sort_header {
             if selected == 0 {
@@ -95,13 +168,23 @@ impl TableManager {
     }
 
     pub fn sort(&mut self) {
+        self.sort_items();
+        // The filter's matches point at the items by their position.
+        self.apply_filter();
+        self.update_rows_number();
+    }
+
+    fn sort_items(&mut self) {
         let sort_by = self
             .sort_header
-            .map(|idx| CONFIG.torrents_tab.headers[idx])
+            .map(|idx| self.layout.headers[idx])
             .unwrap_or(CONFIG.torrents_tab.default_sort);
 
         match sort_by {
-            Header::Id => todo!(),
+            Header::Id => self.table.items.sort_by_key(|t| match t.id {
+                Id::Id(id) => id,
+                Id::Hash(_) => unreachable!(),
+            }),
             Header::Name => self.table.items.sort_by(|x, y| {
                 x.torrent_name
                     .to_lowercase()
@@ -121,23 +204,18 @@ impl TableManager {
                 .table
                 .items
                 .sort_by(|x, y| x.download_speed.cmp(&y.download_speed)),
-            Header::UploadRate => self
-                .table
-                .items
-                .sort_by(|x, y| x.upload_speed.cmp(&y.upload_speed)),
+            Header::UploadRate => self.table.items.sort_by_key(|t| t.upload_speed),
             Header::DownloadDir => self
                 .table
                 .items
                 .sort_by(|x, y| x.download_dir.cmp(&y.download_dir)),
             Header::Padding => (),
-            Header::UploadRatio => self
-                .table
-                .items
-                .sort_by(|x, y| x.upload_ratio.cmp(&y.upload_ratio)),
-            Header::UploadedEver => self
-                .table
-                .items
-                .sort_by(|x, y| x.uploaded_ever.cmp(&y.uploaded_ever)),
+            Header::UploadRatio => self.table.items.sort_by(|x, y| {
+                x.upload_ratio
+                    .partial_cmp(&y.upload_ratio)
+                    .unwrap_or(Ordering::Equal)
+            }),
+            Header::UploadedEver => self.table.items.sort_by_key(|t| t.uploaded_ever),
             Header::ActivityDate => self
                 .table
                 .items
@@ -155,16 +233,54 @@ impl TableManager {
                 x.category
                     .as_ref()
                     .map(|cat| {
-                        cat.name().cmp(
+                        cat.name.as_str().cmp(
                             y.category
                                 .as_ref()
-                                .map(|cat| cat.name())
+                                .map(|cat| cat.name.as_str())
                                 .unwrap_or_default(),
                         )
                     })
                     .unwrap_or(Ordering::Less)
             }),
             Header::CategoryIcon => (),
+            Header::Status => self.table.items.sort_by_key(|t| t.status()),
+            Header::Seeds => self.table.items.sort_by_key(|t| t.seeds),
+            Header::Leeches => self.table.items.sort_by_key(|t| t.leeches),
+            Header::TotalSize => self.table.items.sort_by_key(|t| t.total_size),
+            Header::DownloadedEver => self.table.items.sort_by_key(|t| t.downloaded_ever),
+            Header::DoneDate => self.table.items.sort_by_key(|t| t.done_date),
+            Header::Tracker => self.table.items.sort_by(|x, y| x.tracker.cmp(&y.tracker)),
+            Header::InfoHash => self.table.items.sort_by(|x, y| x.hash.cmp(&y.hash)),
+            Header::QueuePosition => self.table.items.sort_by_key(|t| t.queue_position),
+            Header::Private => self.table.items.sort_by_key(|t| t.is_private),
+            Header::Comment => self.table.items.sort_by(|x, y| x.comment.cmp(&y.comment)),
+            Header::Creator => self.table.items.sort_by(|x, y| x.creator.cmp(&y.creator)),
+            Header::SecondsSeeding => self.table.items.sort_by_key(|t| t.seconds_seeding),
+            Header::Availability => self.table.items.sort_by(|x, y| {
+                x.availability
+                    .partial_cmp(&y.availability)
+                    .unwrap_or(Ordering::Equal)
+            }),
+            Header::Labels => self.table.items.sort_by(|x, y| x.labels.cmp(&y.labels)),
+            Header::DownloadHistory => self
+                .table
+                .items
+                .sort_by_key(|t| SpeedHistory::mean(&t.speed_history.download)),
+            Header::UploadHistory => self
+                .table
+                .items
+                .sort_by_key(|t| SpeedHistory::mean(&t.speed_history.upload)),
+            Header::Custom(idx) => match &CONFIG.torrents_tab.custom_headers[idx].kind {
+                CustomHeaderKind::Expression { expr, .. } => self.table.items.sort_by(|x, y| {
+                    x.eval(expr)
+                        .partial_cmp(&y.eval(expr))
+                        .unwrap_or(Ordering::Equal)
+                }),
+                CustomHeaderKind::Label(regex) => self
+                    .table
+                    .items
+                    .sort_by(|x, y| x.matching_label(regex).cmp(&y.matching_label(regex))),
+            },
         }
         if self.sort_reverse
             || (self.sort_header.is_none() && CONFIG.torrents_tab.default_sort_reverse)
@@ -173,15 +289,263 @@ impl TableManager {
         }
     }
 
+    /// Rebuilds the listed rows after the items, their order, the filter
+    /// or the grouping changed.
     pub fn update_rows_number(&mut self) {
-        if let Some(filter) = &self.filter {
-            self.table.overwrite_len(filter.indexes.len());
+        let torrents: Vec<ListedTorrent> = match &self.filter {
+            Some(filter) => filter
+                .indexes
+                .iter()
+                .enumerate()
+                .map(|(pos, idx)| ListedTorrent {
+                    item: usize::from(*idx),
+                    filter_pos: Some(pos),
+                })
+                .collect(),
+            None => (0..self.table.items.len())
+                .map(|item| ListedTorrent {
+                    item,
+                    filter_pos: None,
+                })
+                .collect(),
+        };
+
+        self.groups = match self.group_by {
+            Some(group_by) => self.group(group_by, torrents.clone()),
+            None => vec![],
+        };
+
+        self.listed = if self.group_by.is_some() {
+            let mut listed = vec![];
+            for (idx, group) in self.groups.iter().enumerate() {
+                listed.push(ListedRow::Group(idx));
+                if !self.collapsed_groups.contains(&group.key) {
+                    listed.extend(group.torrents.iter().copied().map(ListedRow::Torrent));
+                }
+            }
+            listed
         } else {
-            self.table.items.len();
+            torrents.into_iter().map(ListedRow::Torrent).collect()
+        };
+
+        self.table.overwrite_len(self.listed.len());
+        let mut state = self.table.state.borrow_mut();
+        if state
+            .selected()
+            .is_some_and(|selected| selected >= self.listed.len())
+        {
+            state.select(self.listed.len().checked_sub(1));
+        }
+    }
+
+    /// Splits torrents into groups, keeping their order within each group.
+    /// Groups are sorted by name, the one for torrents without a value last.
+    fn group(&self, group_by: GroupBy, torrents: Vec<ListedTorrent>) -> Vec<Group> {
+        let mut groups: Vec<Group> = vec![];
+        let mut group_idxs: HashMap<String, usize> = HashMap::new();
+
+        for listed in torrents {
+            let torrent = &self.table.items[listed.item];
+            let key = group_key(group_by, torrent);
+            let idx = *group_idxs.entry(key.clone()).or_insert_with(|| {
+                groups.push(Group {
+                    key,
+                    torrents: vec![],
+                    size: 0,
+                    download_speed: 0,
+                    upload_speed: 0,
+                });
+                groups.len() - 1
+            });
+
+            let group = &mut groups[idx];
+            group.torrents.push(listed);
+            group.size += torrent.size_when_done;
+            group.download_speed += torrent.download_speed;
+            group.upload_speed += torrent.upload_speed;
+        }
+
+        groups.sort_by_cached_key(|group| (group.key.is_empty(), group.key.to_lowercase()));
+        groups
+    }
+
+    /// Switches to the next grouping, or back to no grouping after the last one.
+    pub fn cycle_grouping(&mut self) {
+        self.group_by = match self.group_by {
+            None => Some(GroupBy::Category),
+            Some(GroupBy::Category) => Some(GroupBy::DownloadDir),
+            Some(GroupBy::DownloadDir) => Some(GroupBy::Tracker),
+            Some(GroupBy::Tracker) => Some(GroupBy::Status),
+            Some(GroupBy::Status) => None,
+        };
+        self.collapsed_groups.clear();
+        self.update_rows_number();
+    }
+
+    /// Collapses every group, or expands them all if they already are.
+    pub fn toggle_all_groups(&mut self) {
+        let all_collapsed = self
+            .groups
+            .iter()
+            .all(|group| self.collapsed_groups.contains(&group.key));
+
+        if all_collapsed {
+            self.collapsed_groups.clear();
+        } else {
+            self.collapsed_groups = self.groups.iter().map(|group| group.key.clone()).collect();
+        }
+
+        let selected_group = self.current_group().map(|idx| self.groups[idx].key.clone());
+        self.update_rows_number();
+        if let Some(key) = selected_group {
+            self.select_group(&key);
+        }
+    }
+
+    /// Collapses or expands the highlighted group, returning whether a group is highlighted.
+    pub fn toggle_current_group(&mut self) -> bool {
+        let Some(idx) = self.current_group() else {
+            return false;
+        };
+
+        let key = self.groups[idx].key.clone();
+        if !self.collapsed_groups.remove(&key) {
+            self.collapsed_groups.insert(key);
+        }
+        self.update_rows_number();
+        true
+    }
+
+    fn select_group(&mut self, key: &str) {
+        let row = self
+            .listed
+            .iter()
+            .position(|row| matches!(row, ListedRow::Group(idx) if self.groups[*idx].key == key));
+        if row.is_some() {
+            self.table.state.borrow_mut().select(row);
+        }
+    }
+
+    /// Index of the group whose header is highlighted.
+    fn current_group(&self) -> Option<usize> {
+        let selected = self.table.state.borrow().selected()?;
+        match self.listed.get(selected)? {
+            ListedRow::Group(idx) => Some(*idx),
+            ListedRow::Torrent(_) => None,
+        }
+    }
+
+    /// IDs of the torrents in the highlighted group.
+    pub fn current_group_ids(&self) -> Option<Vec<i64>> {
+        self.current_group().map(|idx| self.group_ids(idx))
+    }
+
+    fn group_ids(&self, idx: usize) -> Vec<i64> {
+        self.groups[idx]
+            .torrents
+            .iter()
+            .filter_map(|listed| torrent_id(&self.table.items[listed.item]))
+            .collect()
+    }
+
+    fn group_row(&self, group: &Group) -> Row<'_> {
+        let icon = if self.collapsed_groups.contains(&group.key) {
+            "▶"
+        } else {
+            "▼"
+        };
+        let name = match self.group_by {
+            _ if !group.key.is_empty() => group.key.as_str(),
+            Some(GroupBy::Category) => "No category",
+            Some(GroupBy::Tracker) => "No tracker",
+            _ => "None",
+        };
+        let label = format!("{icon} {name} ({})", group.torrents.len());
+
+        let headers = &self.layout.headers;
+        let label_column = headers
+            .iter()
+            .position(|header| *header == Header::Name)
+            .unwrap_or_default();
+        let speed = |speed: i64| match speed {
+            0 => String::new(),
+            speed => bytes_to_human_format(speed),
+        };
+
+        headers
+            .iter()
+            .enumerate()
+            .map(|(idx, header)| match header {
+                _ if idx == label_column => label.clone(),
+                Header::SizeWhenDone => bytes_to_human_format(group.size),
+                Header::DownloadRate => speed(group.download_speed),
+                Header::UploadRate => speed(group.upload_speed),
+                _ => String::new(),
+            })
+            .collect::<Row>()
+            .style(Style::default().fg(CONFIG.theme.accent).bold())
+    }
+
+    /// Heights of the listed rows. Torrents with an error take two rows.
+    pub fn row_heights(&self) -> impl Iterator<Item = u16> + '_ {
+        self.listed.iter().map(|row| match row {
+            ListedRow::Torrent(listed) if self.table.items[listed.item].error.is_some() => 2,
+            _ => 1,
+        })
+    }
+
+    /// Highlights the torrent with this ID, clearing the filter if it hides it.
+    pub fn highlight_torrent(&mut self, id: i64) {
+        let Some(idx) = self
+            .table
+            .items
+            .iter()
+            .position(|torrent| torrent.id == Id::Id(id))
+        else {
+            return;
+        };
+
+        let is_listed = self.filter.as_ref().is_none_or(|filter| {
+            filter
+                .indexes
+                .iter()
+                .any(|filtered| usize::from(*filtered) == idx)
+        });
+        if !is_listed {
+            self.filter = None;
+        }
+        if let Some(group_by) = self.group_by {
+            self.collapsed_groups
+                .remove(&group_key(group_by, &self.table.items[idx]));
+        }
+        self.update_rows_number();
+
+        let row = self
+            .listed
+            .iter()
+            .position(|row| matches!(row, ListedRow::Torrent(listed) if listed.item == idx));
+        if row.is_some() {
+            self.table.state.borrow_mut().select(row);
         }
     }
 
     pub fn select_current_torrent(&mut self) {
+        // Selecting a group's header selects the whole group, or deselects it
+        // if it's selected already.
+        if let Some(ids) = self.current_group_ids() {
+            if ids.iter().all(|id| self.selected_torrents_ids.contains(id)) {
+                self.selected_torrents_ids.retain(|id| !ids.contains(id));
+            } else {
+                for id in ids {
+                    if !self.selected_torrents_ids.contains(&id) {
+                        self.selected_torrents_ids.push(id);
+                    }
+                }
+            }
+            self.sync_selection();
+            return;
+        }
+
         let mut is_selected = true;
         if let Some(t) = self.current_torrent() {
             if let Id::Id(id) = t.id {
@@ -204,52 +568,228 @@ impl TableManager {
         }
     }
 
-    pub fn rows(&self) -> Vec<Row<'_>> {
-        if let Some(filter) = &self.filter {
-            let highlight_style = Style::default().fg(CONFIG.general.accent_color);
-            let headers = &CONFIG.torrents_tab.headers;
-            let mut rows = vec![];
-            for (i, which_torrent) in filter.indexes.iter().enumerate() {
-                let row = self.table.items[*which_torrent as usize].to_row_with_higlighted_indices(
-                    &filter.highlight_indices[i],
-                    highlight_style,
-                    headers,
-                );
-                rows.push(row);
-            }
+    pub fn is_visual_selecting(&self) -> bool {
+        self.visual_selection.is_some()
+    }
+
+    pub fn start_visual_selection(&mut self) {
+        let Some(anchor) = self.table.state.borrow().selected() else {
+            return;
+        };
 
-            self.table.overwrite_len(rows.len());
-            rows
+        self.visual_selection = Some(VisualSelection {
+            anchor,
+            base: self.selected_torrents_ids.clone(),
+        });
+        self.update_visual_selection();
+    }
+
+    /// Selects every row between the anchor and the current row, on top of
+    /// what was selected before.
+    pub fn update_visual_selection(&mut self) {
+        let Some(visual) = &self.visual_selection else {
+            return;
+        };
+        let Some(current) = self.table.state.borrow().selected() else {
+            return;
+        };
+
+        let (start, end) = if visual.anchor <= current {
+            (visual.anchor, current)
         } else {
-            self.table
-                .items
+            (current, visual.anchor)
+        };
+
+        let mut selected = visual.base.clone();
+        let mut ids = vec![];
+        for row in self.listed.iter().skip(start).take(end - start + 1) {
+            match row {
+                ListedRow::Torrent(listed) => {
+                    ids.extend(torrent_id(&self.table.items[listed.item]))
+                }
+                // Torrents of expanded groups are in the range on their own.
+                ListedRow::Group(idx) if self.collapsed_groups.contains(&self.groups[*idx].key) => {
+                    ids.extend(self.group_ids(*idx))
+                }
+                ListedRow::Group(_) => (),
+            }
+        }
+        for id in ids {
+            if !selected.contains(&id) {
+                selected.push(id);
+            }
+        }
+
+        self.selected_torrents_ids = selected;
+        self.sync_selection();
+    }
+
+    pub fn confirm_visual_selection(&mut self) {
+        self.visual_selection = None;
+    }
+
+    pub fn cancel_visual_selection(&mut self) {
+        if let Some(visual) = self.visual_selection.take() {
+            self.selected_torrents_ids = visual.base;
+            self.sync_selection();
+        }
+    }
+
+    /// Selects all torrents that are listed, i.e. the ones matching the
+    /// filter if there is one.
+    pub fn select_all(&mut self) {
+        let selected: HashSet<_> = self.selected_torrents_ids.iter().copied().collect();
+        for id in self.listed_ids() {
+            if !selected.contains(&id) {
+                self.selected_torrents_ids.push(id);
+            }
+        }
+        self.sync_selection();
+    }
+
+    /// Inverts selection of the listed torrents, leaving torrents hidden by
+    /// the filter untouched.
+    pub fn invert_selection(&mut self) {
+        let listed = self.listed_ids();
+        let listed_set: HashSet<_> = listed.iter().copied().collect();
+        let previously_selected: HashSet<_> = self.selected_torrents_ids.iter().copied().collect();
+
+        self.selected_torrents_ids
+            .retain(|id| !listed_set.contains(id));
+        self.selected_torrents_ids.extend(
+            listed
+                .into_iter()
+                .filter(|id| !previously_selected.contains(id)),
+        );
+        self.sync_selection();
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.visual_selection = None;
+        self.selected_torrents_ids.clear();
+        self.sync_selection();
+    }
+
+    /// Combined size of the selected torrents.
+    pub fn selection_size(&self) -> i64 {
+        self.table
+            .items
+            .iter()
+            .filter(|torrent| torrent.is_selected)
+            .map(|torrent| torrent.size_when_done)
+            .sum()
+    }
+
+    /// IDs of the torrents matching the filter, collapsed groups included.
+    fn listed_ids(&self) -> Vec<i64> {
+        if let Some(filter) = &self.filter {
+            filter
+                .indexes
                 .iter()
-                .map(|t| t.to_row(&CONFIG.torrents_tab.headers))
+                .filter_map(|idx| torrent_id(&self.table.items[*idx as usize]))
                 .collect()
+        } else {
+            self.table.items.iter().filter_map(torrent_id).collect()
         }
     }
 
+    /// Index of the listed row (a torrent or a group's header) on the given
+    /// terminal row of the table's body.
+    pub fn listed_index_at(&self, body: Rect, row: u16) -> Option<usize> {
+        if row < body.y || row >= body.bottom() {
+            return None;
+        }
+
+        let offset = self.table.state.borrow().offset();
+        let mut y = body.y;
+        for (idx, height) in self.row_heights().enumerate().skip(offset) {
+            if row < y + height {
+                return Some(idx);
+            }
+            y += height;
+        }
+        None
+    }
+
+    fn sync_selection(&mut self) {
+        let selected: HashSet<_> = self.selected_torrents_ids.iter().collect();
+        for torrent in &mut self.table.items {
+            if let Id::Id(id) = &torrent.id {
+                torrent.is_selected = selected.contains(id);
+            }
+        }
+    }
+
+    pub fn rows(&self) -> Vec<Row<'_>> {
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+        let headers = &self.layout.headers;
+
+        self.listed
+            .iter()
+            .map(|row| match row {
+                ListedRow::Group(idx) => self.group_row(&self.groups[*idx]),
+                ListedRow::Torrent(listed) => {
+                    let torrent = &self.table.items[listed.item];
+                    match (&self.filter, listed.filter_pos) {
+                        (Some(filter), Some(pos)) => torrent.to_row_with_higlighted_indices(
+                            &filter.highlight_indices[pos],
+                            highlight_style,
+                            headers,
+                        ),
+                        _ => torrent.to_row(headers),
+                    }
+                }
+            })
+            .collect()
+    }
+
     pub fn headers(&self) -> &Vec<Header> {
-        &CONFIG.torrents_tab.headers
+        &self.layout.headers
+    }
+
+    pub fn layout(&self) -> &ColumnLayout {
+        &self.layout
+    }
+
+    /// Switches to another column layout, keeping the sort if its column is still shown.
+    pub fn set_layout(&mut self, layout: ColumnLayout) {
+        let sorted_by = self.sort_header.map(|idx| self.layout.headers[idx]);
+        self.sort_header =
+            sorted_by.and_then(|header| layout.headers.iter().position(|h| *h == header));
+        if self.sort_header.is_none() {
+            self.sorting_is_being_selected = false;
+        }
+
+        self.layout = layout;
+        self.widths = self.header_widths(&self.table.items);
+        self.sort();
     }
 
+    /// The highlighted torrent, none if a group's header is highlighted.
     pub fn current_torrent(&mut self) -> Option<&mut RustmissionTorrent> {
         let selected_idx = self.table.state.borrow().selected()?;
 
-        if let Some(filter) = &self.filter {
-            if filter.indexes.is_empty() {
-                None
-            } else {
-                self.table
-                    .items
-                    .get_mut(filter.indexes[selected_idx] as usize)
-            }
-        } else {
-            self.table.items.get_mut(selected_idx)
+        match self.listed.get(selected_idx)? {
+            ListedRow::Torrent(listed) => self.table.items.get_mut(listed.item),
+            ListedRow::Group(_) => None,
         }
     }
 
     pub fn set_new_rows(&mut self, mut rows: Vec<RustmissionTorrent>) {
+        let previous_items = std::mem::take(&mut self.table.items);
+        let previous_order: Vec<Id> = previous_items.iter().map(|t| t.id.clone()).collect();
+        let mut histories: HashMap<_, _> = previous_items
+            .into_iter()
+            .map(|torrent| (torrent.id, torrent.speed_history))
+            .collect();
+
+        for row in &mut rows {
+            if let Some(mut history) = histories.remove(&row.id) {
+                history.push(row.download_speed, row.upload_speed);
+                row.speed_history = history;
+            }
+        }
+
         if !self.selected_torrents_ids.is_empty() {
             let mut found_ids = vec![];
 
@@ -272,10 +812,25 @@ impl TableManager {
             self.selected_torrents_ids = new_selected;
         }
 
+        let previous_matches = self.filter.as_ref().map(|filter| filter.indexes.clone());
+
         self.table.set_items(rows);
         self.widths = self.header_widths(&self.table.items);
-        self.update_rows_number();
-        self.sort();
+        self.sort_items();
+        self.apply_filter();
+
+        // Groups have their members and totals change with every update, but
+        // otherwise the rows only change if torrents or the filter's matches moved.
+        let is_unchanged = self.group_by.is_none()
+            && previous_order.len() == self.table.items.len()
+            && previous_order
+                .iter()
+                .zip(&self.table.items)
+                .all(|(id, torrent)| *id == torrent.id)
+            && previous_matches == self.filter.as_ref().map(|filter| filter.indexes.clone());
+        if !is_unchanged {
+            self.update_rows_number();
+        }
 
         let mut state = self.table.state.borrow_mut();
         if state.selected().is_none() && !self.table.items.is_empty() {
@@ -284,95 +839,188 @@ impl TableManager {
     }
 
     pub fn set_filter(&mut self, filter: String) {
+        self.filter = Some(Filter {
+            pattern: filter,
+            indexes: vec![],
+            highlight_indices: vec![],
+            matches: HashMap::new(),
+        });
+        self.apply_filter();
+    }
+
+    /// Finds the filter's matches among the items in their current order. Only
+    /// torrents that are new or have their name or labels changed are matched again.
+    fn apply_filter(&mut self) {
+        let Some(filter) = &mut self.filter else {
+            return;
+        };
+
+        // Words like "label:anime" narrow the results down to torrents having
+        // a label starting with "anime", the rest is fuzzy matched against names.
+        let mut labels = vec![];
+        let mut name_pattern = vec![];
+        for word in filter.pattern.split_whitespace() {
+            match word.strip_prefix("label:") {
+                Some(label) => labels.push(label.to_lowercase()),
+                None => name_pattern.push(word),
+            }
+        }
+        let name_pattern = name_pattern.join(" ");
+
         let matcher = SkimMatcherV2::default();
-        let mut indexes: Vec<u16> = vec![];
-        let mut highlight_indices = vec![];
+        let mut previous_matches = std::mem::take(&mut filter.matches);
+        filter.indexes.clear();
+        filter.highlight_indices.clear();
+
         for (i, torrent) in self.table.items.iter().enumerate() {
-            if let Some((_, indices)) = matcher.fuzzy_indices(&torrent.torrent_name, &filter) {
-                indexes.push(i as u16);
-                highlight_indices.push(indices);
+            let filter_match = previous_matches
+                .remove(&torrent.id)
+                .filter(|previous| {
+                    previous.name == torrent.torrent_name && previous.labels == torrent.labels
+                })
+                .unwrap_or_else(|| {
+                    let has_labels = labels.iter().all(|wanted| {
+                        torrent
+                            .labels
+                            .iter()
+                            .any(|label| label.to_lowercase().starts_with(wanted))
+                    });
+
+                    let highlight_indices = if !has_labels {
+                        None
+                    } else if name_pattern.is_empty() {
+                        Some(vec![])
+                    } else {
+                        matcher
+                            .fuzzy_indices(&torrent.torrent_name, &name_pattern)
+                            .map(|(_, indices)| indices)
+                    };
+
+                    FilterMatch {
+                        name: torrent.torrent_name.clone(),
+                        labels: torrent.labels.clone(),
+                        highlight_indices,
+                    }
+                });
+
+            if let Some(indices) = &filter_match.highlight_indices {
+                filter.indexes.push(i as u16);
+                filter.highlight_indices.push(indices.clone());
             }
+            filter.matches.insert(torrent.id.clone(), filter_match);
         }
+    }
 
-        let filter = Filter {
-            pattern: filter,
-            indexes,
-            highlight_indices,
-        };
+    pub fn speed_history_of(&self, id: &Id) -> Option<&SpeedHistory> {
+        self.table
+            .items
+            .iter()
+            .find(|torrent| torrent.id == *id)
+            .map(|torrent| &torrent.speed_history)
+    }
 
-        self.filter = Some(filter);
+    pub fn all_labels(&self) -> Vec<String> {
+        self.table
+            .items
+            .iter()
+            .flat_map(|torrent| torrent.labels.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 
-    fn default_widths(headers: &Vec<Header>) -> Vec<Constraint> {
-        let mut constraints = vec![];
+    pub fn all_download_dirs(&self) -> Vec<String> {
+        self.table
+            .items
+            .iter()
+            .map(|torrent| torrent.download_dir.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
 
-        for header in headers {
-            if *header == Header::Category {
-                constraints.push(Constraint::Length(u16::from(
-                    CONFIG.categories.max_name_len,
-                )))
-            } else if *header == Header::CategoryIcon {
-                constraints.push(Constraint::Length(u16::from(
-                    CONFIG.categories.max_icon_len,
-                )))
-            } else {
-                constraints.push(header.default_constraint())
-            }
-        }
-        constraints
+    pub fn labels_of(&self, ids: &[Id]) -> Vec<String> {
+        self.table
+            .items
+            .iter()
+            .filter(|torrent| ids.contains(&torrent.id))
+            .flat_map(|torrent| torrent.labels.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 
-    fn header_widths(&self, rows: &[RustmissionTorrent]) -> Vec<Constraint> {
-        let headers = &CONFIG.torrents_tab.headers;
+    fn default_widths(layout: &ColumnLayout) -> Vec<Constraint> {
+        let mut constraints = vec![];
 
-        if !CONFIG.general.auto_hide {
-            return Self::default_widths(headers);
+        for (idx, header) in layout.headers.iter().enumerate() {
+            constraints.push(Self::header_constraint(layout, idx, header));
         }
+        constraints
+    }
 
-        let mut map = HashMap::new();
+    fn header_constraint(layout: &ColumnLayout, idx: usize, header: &Header) -> Constraint {
+        layout
+            .width(idx)
+            .map(Constraint::Length)
+            .unwrap_or_else(|| Self::default_header_constraint(header))
+    }
 
-        for header in headers {
-            map.insert(header, header.default_constraint());
+    /// Width of a header the user hasn't resized.
+    pub fn default_header_constraint(header: &Header) -> Constraint {
+        match header {
+            Header::Category => Constraint::Length(u16::from(CONFIG.categories.max_name_len)),
+            Header::CategoryIcon => Constraint::Length(u16::from(CONFIG.categories.max_icon_len)),
+            Header::Custom(idx) => CONFIG.torrents_tab.custom_headers[*idx]
+                .width
+                .map(Constraint::Length)
+                .unwrap_or_else(|| header.default_constraint()),
+            header => header.default_constraint(),
         }
+    }
 
-        let hidable_headers = [
-            Header::Progress,
-            Header::UploadRate,
-            Header::DownloadRate,
-            Header::Eta,
-        ];
+    fn header_widths(&self, rows: &[RustmissionTorrent]) -> Vec<Constraint> {
+        let mut constraints = Self::default_widths(&self.layout);
 
-        for hidable_header in &hidable_headers {
-            map.entry(hidable_header)
-                .and_modify(|c| *c = Constraint::Length(0));
+        if !CONFIG.general.auto_hide {
+            return constraints;
         }
 
-        for row in rows {
-            if !row.download_speed().is_empty() {
-                map.entry(&Header::DownloadRate)
-                    .and_modify(|c| *c = Header::DownloadRate.default_constraint());
-            }
-            if !row.upload_speed.is_empty() {
-                map.entry(&Header::UploadRate)
-                    .and_modify(|c| *c = Header::UploadRate.default_constraint());
-            }
-            if !row.progress().is_empty() {
-                map.entry(&Header::Progress)
-                    .and_modify(|c| *c = Header::Progress.default_constraint());
-            }
+        let has_values = |header: &Header| match header {
+            Header::DownloadRate => rows.iter().any(|row| !row.download_speed().is_empty()),
+            Header::UploadRate => rows.iter().any(|row| row.upload_speed != 0),
+            Header::Progress => rows.iter().any(|row| !row.progress().is_empty()),
+            Header::Eta => rows.iter().any(|row| !row.eta_secs().is_empty()),
+            _ => true,
+        };
 
-            if !row.eta_secs().is_empty() {
-                map.entry(&Header::Eta)
-                    .and_modify(|c| *c = Header::Eta.default_constraint());
+        for (constraint, header) in constraints.iter_mut().zip(&self.layout.headers) {
+            if !has_values(header) {
+                *constraint = Constraint::Length(0);
             }
         }
 
-        let mut constraints = vec![];
+        constraints
+    }
+}
 
-        for header in headers {
-            constraints.push(map.remove(header).expect("this header exists"))
-        }
+/// Selection is kept by numeric IDs, torrents known only by a hash are skipped.
+fn torrent_id(torrent: &RustmissionTorrent) -> Option<i64> {
+    match torrent.id {
+        Id::Id(id) => Some(id),
+        Id::Hash(_) => None,
+    }
+}
 
-        constraints
+fn group_key(group_by: GroupBy, torrent: &RustmissionTorrent) -> String {
+    match group_by {
+        GroupBy::Category => torrent
+            .category
+            .as_ref()
+            .map(|category| category.name.clone())
+            .unwrap_or_default(),
+        GroupBy::DownloadDir => torrent.download_dir.clone(),
+        GroupBy::Tracker => torrent.tracker.clone().unwrap_or_default(),
+        GroupBy::Status => torrent.status_str().to_string(),
     }
 }
@@ -40,6 +40,7 @@ pub enum CurrentTask {
     Filter(tasks::Filter),
     Move(tasks::Move),
     ChangeCategory(tasks::ChangeCategory),
+    EditLabel(tasks::EditLabel),
     Default(tasks::Default),
     Status(tasks::Status),
     Sort(tasks::Sort),
@@ -94,6 +95,11 @@ impl Component for TaskManager {
                     self.cancel_task()
                 }
             }
+            CurrentTask::EditLabel(label_bar) => {
+                if label_bar.handle_actions(action).is_quit() {
+                    self.cancel_task()
+                }
+            }
             CurrentTask::Default(_) => (),
             CurrentTask::Sort(_) => (),
             CurrentTask::Selection(_) => (),
@@ -132,6 +138,7 @@ impl Component for TaskManager {
             CurrentTask::Sort(sort_bar) => sort_bar.render(f, rect),
             CurrentTask::Selection(selection_bar) => selection_bar.render(f, rect),
             CurrentTask::Rename(rename_bar) => rename_bar.render(f, rect),
+            CurrentTask::EditLabel(label_bar) => label_bar.render(f, rect),
         }
     }
 
@@ -141,8 +148,17 @@ impl Component for TaskManager {
 }
 
 impl TaskManager {
-    pub fn add_magnet(&mut self) {
-        self.current_task = CurrentTask::AddMagnet(tasks::AddMagnet::new());
+    pub fn add_magnet(&mut self, known_dirs: Vec<String>) {
+        self.current_task = CurrentTask::AddMagnet(tasks::AddMagnet::new().known_dirs(known_dirs));
+        CTX.send_update_action(UpdateAction::SwitchToInputMode);
+    }
+
+    pub fn paste_magnet(&mut self, magnet: String, known_dirs: Vec<String>) {
+        self.current_task = CurrentTask::AddMagnet(
+            tasks::AddMagnet::new()
+                .known_dirs(known_dirs)
+                .magnet(magnet),
+        );
         CTX.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
@@ -161,8 +177,13 @@ impl TaskManager {
         CTX.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
-    pub fn move_torrent(&mut self, selection: TorrentSelection, current_dir: String) {
-        self.current_task = CurrentTask::Move(tasks::Move::new(selection, current_dir));
+    pub fn move_torrent(
+        &mut self,
+        selection: TorrentSelection,
+        current_dir: String,
+        known_dirs: Vec<String>,
+    ) {
+        self.current_task = CurrentTask::Move(tasks::Move::new(selection, current_dir, known_dirs));
         CTX.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
@@ -171,12 +192,23 @@ impl TaskManager {
         CTX.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
+    pub fn edit_label(
+        &mut self,
+        selection: TorrentSelection,
+        edit: tasks::LabelEdit,
+        autocompletions: Vec<String>,
+    ) {
+        self.current_task =
+            CurrentTask::EditLabel(tasks::EditLabel::new(selection, edit, autocompletions));
+        CTX.send_update_action(UpdateAction::SwitchToInputMode);
+    }
+
     pub fn default(&mut self) {
         self.current_task = CurrentTask::Default(tasks::Default::new());
     }
 
-    pub fn select(&mut self, amount: usize) {
-        self.current_task = CurrentTask::Selection(tasks::Selection::new(amount));
+    pub fn select(&mut self, amount: usize, size: i64, is_visual: bool) {
+        self.current_task = CurrentTask::Selection(tasks::Selection::new(amount, size, is_visual));
     }
 
     pub fn sort(&mut self) {
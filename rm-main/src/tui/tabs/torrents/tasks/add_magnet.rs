@@ -1,27 +1,45 @@
+use std::path::PathBuf;
+
 use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::prelude::*;
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Paragraph},
+};
 use rm_config::CONFIG;
 
+use super::directory_input::DirectoryInput;
 use crate::{
     transmission::TorrentAction,
     tui::{
         components::{Component, ComponentAction, InputManager},
         ctx::CTX,
-        tabs::torrents::SESSION_GET,
+        tabs::torrents::{popups::torrent_picker::read_pending, SESSION_GET},
     },
 };
 use rm_shared::{
-    action::{Action, UpdateAction},
+    action::{Action, ErrorMessage, UpdateAction},
+    magnet::{Link, LinkError},
+    metainfo::PendingTorrent,
     status_task::StatusTask,
 };
 
 pub struct AddMagnet {
     input_magnet_mgr: InputManager,
     input_category_mgr: InputManager,
-    input_location_mgr: InputManager,
+    input_location_mgr: DirectoryInput,
+    /// Local .torrent file that gets previewed instead of being added right away.
+    pending: Option<Box<PendingTorrent>>,
     stage: Stage,
 }
 
+/// What the text typed in the first stage refers to.
+enum Source {
+    Link(Link),
+    LocalFile(PathBuf),
+    /// A .torrent file on the daemon's machine.
+    DaemonPath,
+}
+
 enum Stage {
     Magnet,
     Category,
@@ -35,23 +53,29 @@ const LOCATION_PROMPT: &str = "Directory: ";
 impl AddMagnet {
     pub fn new() -> Self {
         Self {
-            input_magnet_mgr: InputManager::new(MAGNET_PROMPT.to_string()),
+            input_magnet_mgr: InputManager::new(MAGNET_PROMPT.to_string()).history("magnet"),
             input_category_mgr: InputManager::new(CATEGORY_PROMPT.to_string())
                 .autocompletions(CONFIG.categories.map.keys().cloned().collect()),
-            input_location_mgr: InputManager::new_with_value(
-                LOCATION_PROMPT.to_string(),
+            input_location_mgr: DirectoryInput::new(
+                LOCATION_PROMPT,
                 SESSION_GET.get().unwrap().download_dir.clone(),
-            ),
+            )
+            .history("directory"),
+            pending: None,
             stage: Stage::Magnet,
         }
     }
 
+    pub fn known_dirs(mut self, dirs: Vec<String>) -> Self {
+        self.input_location_mgr = self.input_location_mgr.known_dirs(dirs);
+        self
+    }
+
     pub fn magnet(mut self, magnet: impl Into<String>) -> Self {
         self.input_magnet_mgr.set_text(magnet);
-        if CONFIG.categories.is_empty() {
-            self.stage = Stage::Location
-        } else {
-            self.stage = Stage::Category;
+        // Stays at the first stage so that the user sees what's wrong with it.
+        if let Ok(source) = self.source() {
+            self.confirm_source(source);
         }
 
         self
@@ -65,14 +89,95 @@ impl AddMagnet {
         }
     }
 
+    fn source(&self) -> Result<Source, LinkError> {
+        let text = self.input_magnet_mgr.text();
+        if let Some(path) = local_torrent_file(&text) {
+            return Ok(Source::LocalFile(path));
+        }
+        match Link::parse(&text) {
+            // Anything else, relative and Windows paths too, is up to the daemon.
+            Err(LinkError::NotALink) => Ok(Source::DaemonPath),
+            result => result.map(Source::Link),
+        }
+    }
+
+    /// Describes what was typed in, or what's wrong with it.
+    fn source_hint(&self) -> Option<Line<'static>> {
+        if self.input_magnet_mgr.text().trim().is_empty() {
+            return None;
+        }
+
+        let highlight_style = Style::default().fg(CONFIG.theme.accent);
+        let line = match self.source() {
+            Ok(Source::Link(Link::Magnet(magnet))) => {
+                let name = magnet.display_name.unwrap_or_else(|| "unnamed".to_string());
+                let hash = match (magnet.info_hash, magnet.info_hash_v2) {
+                    (Some(hash), _) => format!("btih {hash}"),
+                    (None, Some(hash)) => format!("btmh {hash}"),
+                    (None, None) => unreachable!("magnet links are parsed with a hash"),
+                };
+                let trackers = match magnet.trackers.len() {
+                    1 => "1 tracker".to_string(),
+                    len => format!("{len} trackers"),
+                };
+                let mut line = Line::from(vec![
+                    Span::styled(name, highlight_style),
+                    Span::raw(format!(" | {hash} | {trackers}")),
+                ]);
+                if !magnet.skipped_trackers.is_empty() {
+                    line.push_span(Span::styled(
+                        format!(" | {} unusable skipped", magnet.skipped_trackers.len()),
+                        Style::default().fg(CONFIG.theme.warning),
+                    ));
+                }
+                line
+            }
+            Ok(Source::Link(Link::Url(url))) => Line::from(vec![
+                Span::raw(".torrent file from "),
+                Span::styled(
+                    url.host_str().unwrap_or_default().to_string(),
+                    highlight_style,
+                ),
+            ]),
+            Ok(Source::LocalFile(_)) => Line::from("Local .torrent file, previewed before adding"),
+            Ok(Source::DaemonPath) => Line::from("Path on the daemon's machine, if it's there"),
+            Err(e) => Line::from(e.to_string()).fg(CONFIG.theme.error),
+        };
+        Some(line)
+    }
+
+    /// Moves on from the first stage, reading the torrent first if it's a local
+    /// file. Returns whether it moved on.
+    fn confirm_source(&mut self, source: Source) -> bool {
+        if let Source::LocalFile(path) = source {
+            match read_pending(&path) {
+                Ok(pending) => self.pending = Some(Box::new(pending)),
+                Err(e) => {
+                    let desc = format!("Failed to read \"{}\"", path.display());
+                    let err_msg = ErrorMessage::new("Failed to add a torrent", desc, e);
+                    CTX.send_update_action(UpdateAction::Error(Box::new(err_msg)));
+                    return false;
+                }
+            }
+        }
+        if CONFIG.categories.is_empty() {
+            self.stage = Stage::Location;
+        } else {
+            self.stage = Stage::Category;
+        }
+        true
+    }
+
     fn handle_magnet_input(&mut self, input: KeyEvent) -> ComponentAction {
         if input.code == KeyCode::Enter {
-            if CONFIG.categories.is_empty() {
-                self.stage = Stage::Location;
-            } else {
-                self.stage = Stage::Category;
+            let Ok(source) = self.source() else {
+                // The hint already tells what's wrong.
+                return ComponentAction::Nothing;
+            };
+            self.input_magnet_mgr.save_history();
+            if self.confirm_source(source) {
+                CTX.send_action(Action::Render);
             }
-            CTX.send_action(Action::Render);
             return ComponentAction::Nothing;
         }
 
@@ -96,17 +201,15 @@ impl AddMagnet {
             } else if let Some(category) =
                 CONFIG.categories.map.get(&self.input_category_mgr.text())
             {
-                self.input_location_mgr = InputManager::new_with_value(
-                    LOCATION_PROMPT.to_string(),
-                    category.default_dir.clone().unwrap_or_else(|| {
+                self.input_location_mgr
+                    .set_text(category.default_dir.clone().unwrap_or_else(|| {
                         SESSION_GET
                             .get()
                             .as_ref()
                             .expect("session_get was already initialized")
                             .download_dir
                             .clone()
-                    }),
-                );
+                    }));
                 self.stage = Stage::Location;
                 CTX.send_action(Action::Render);
                 return ComponentAction::Nothing;
@@ -133,12 +236,20 @@ impl AddMagnet {
 
     fn handle_location_input(&mut self, input: KeyEvent) -> ComponentAction {
         if input.code == KeyCode::Enter {
+            self.input_location_mgr.save_history();
             let category = if self.input_category_mgr.text().is_empty() {
                 None
             } else {
                 Some(self.input_category_mgr.text())
             };
 
+            if let Some(mut pending) = self.pending.take() {
+                pending.download_dir = Some(self.input_location_mgr.text());
+                pending.category = category;
+                CTX.send_update_action(UpdateAction::PreviewTorrent(pending));
+                return ComponentAction::Quit;
+            }
+
             let torrent_action = TorrentAction::Add(
                 self.input_magnet_mgr.text(),
                 Some(self.input_location_mgr.text()),
@@ -166,15 +277,51 @@ impl Component for AddMagnet {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         match action {
             Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                let response = match self.stage {
+                    Stage::Magnet => self.input_magnet_mgr.paste(&text),
+                    Stage::Category => self.input_category_mgr.paste(&text),
+                    Stage::Location => self.input_location_mgr.paste(&text),
+                };
+                if response.is_some() {
+                    CTX.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
 
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         match self.stage {
-            Stage::Magnet => self.input_magnet_mgr.render(f, rect),
+            Stage::Magnet => {
+                if let Some(hint) = self.source_hint().filter(|_| rect.y > 0) {
+                    let hint_rect = Rect {
+                        y: rect.y - 1,
+                        height: 1,
+                        ..rect
+                    };
+                    f.render_widget(Clear, hint_rect);
+                    f.render_widget(Paragraph::new(hint), hint_rect);
+                }
+                self.input_magnet_mgr.render(f, rect);
+            }
             Stage::Category => self.input_category_mgr.render(f, rect),
             Stage::Location => self.input_location_mgr.render(f, rect),
         }
     }
 }
+
+/// Path of a .torrent file on this machine, if that's what was typed in.
+fn local_torrent_file(text: &str) -> Option<PathBuf> {
+    let text = text.trim();
+    if !text.ends_with(".torrent") || text.contains("://") || text.starts_with("magnet:") {
+        return None;
+    }
+
+    let path = match text.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var_os("HOME")?).join(rest),
+        None => PathBuf::from(text),
+    };
+    path.is_file().then_some(path)
+}
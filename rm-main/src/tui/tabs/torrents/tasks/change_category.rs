@@ -148,6 +148,16 @@ impl Component for ChangeCategory {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         match action {
             Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                let input_mgr = match self.stage {
+                    Stage::Category => &mut self.category_input_mgr,
+                    Stage::Directory => &mut self.directory_input_mgr,
+                };
+                if input_mgr.paste(&text).is_some() {
+                    CTX.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
@@ -0,0 +1,265 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Paragraph},
+};
+use rm_config::{keymap::InputAction, CONFIG};
+use tui_input::{InputResponse, StateChanged};
+
+use crate::tui::{
+    components::{input_action, keybinding_style, Component, InputManager},
+    tabs::torrents::SESSION_GET,
+};
+
+/// Input for a directory on the daemon's machine.
+///
+/// Tab completes from directories that torrents and categories already use,
+/// and from the filesystem when it's reachable (see `path_mappings`).
+/// Bookmarks from config.toml can be picked with 1..9 before typing anything.
+pub struct DirectoryInput {
+    input_mgr: InputManager,
+    known_dirs: BTreeSet<String>,
+    picking_bookmark: bool,
+    listed_parent: Option<String>,
+    listing: Arc<Mutex<Listing>>,
+}
+
+/// Subdirectories of a local directory, listed in the background so that
+/// slow filesystems don't block the UI.
+#[derive(Default)]
+struct Listing {
+    parent: String,
+    subdirs: Vec<String>,
+}
+
+impl DirectoryInput {
+    pub fn new(prompt: impl Into<String>, value: impl Into<String>) -> Self {
+        let mut known_dirs: BTreeSet<String> = CONFIG
+            .categories
+            .map
+            .values()
+            .filter_map(|category| category.default_dir.clone())
+            .chain(CONFIG.torrents_tab.directory_bookmarks.iter().cloned())
+            .collect();
+
+        if let Some(session_get) = SESSION_GET.get() {
+            known_dirs.insert(session_get.download_dir.clone());
+        }
+
+        let mut input = Self {
+            input_mgr: InputManager::new_with_value(prompt.into(), value.into()),
+            known_dirs,
+            picking_bookmark: !CONFIG.torrents_tab.directory_bookmarks.is_empty(),
+            listed_parent: None,
+            listing: Arc::default(),
+        };
+        input.list_parent();
+        input
+    }
+
+    pub fn known_dirs(mut self, dirs: impl IntoIterator<Item = String>) -> Self {
+        self.known_dirs.extend(dirs);
+        self
+    }
+
+    pub fn history(mut self, name: &'static str) -> Self {
+        self.input_mgr = self.input_mgr.history(name);
+        self
+    }
+
+    pub fn save_history(&mut self) {
+        self.input_mgr.save_history();
+    }
+
+    pub fn text(&self) -> String {
+        self.input_mgr.text()
+    }
+
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.input_mgr.set_text(text);
+        self.list_parent();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResponse {
+        let changed = Some(StateChanged {
+            value: true,
+            cursor: true,
+        });
+
+        if self.is_picking_bookmark() {
+            self.picking_bookmark = false;
+            if let (KeyCode::Char(c @ '1'..='9'), KeyModifiers::NONE) = (key.code, key.modifiers) {
+                let idx = c as usize - '1' as usize;
+                if let Some(bookmark) = CONFIG.torrents_tab.directory_bookmarks.get(idx) {
+                    self.set_text(bookmark.clone());
+                    return changed;
+                }
+            }
+        }
+
+        let action = input_action(&key);
+        let is_completion = matches!(
+            action,
+            Some(InputAction::NextCompletion | InputAction::PrevCompletion)
+        );
+
+        if is_completion && !self.input_mgr.is_completing() {
+            let text = self.text();
+            let candidates = self.candidates(&text);
+            if candidates.is_empty() {
+                if !text.ends_with('/') && self.is_listed_dir(&text) {
+                    self.set_text(format!("{text}/"));
+                }
+                return changed;
+            }
+            self.input_mgr.set_autocompletions(candidates);
+        }
+
+        let response = self.input_mgr.handle_key(key);
+        // Candidates are only valid for the text they were looked up for.
+        if !is_completion && !self.input_mgr.is_completing() {
+            self.input_mgr.set_autocompletions(vec![]);
+        }
+        self.list_parent();
+        response
+    }
+
+    pub fn paste(&mut self, text: &str) -> InputResponse {
+        self.picking_bookmark = false;
+        let response = self.input_mgr.paste(text);
+        self.input_mgr.set_autocompletions(vec![]);
+        self.list_parent();
+        response
+    }
+
+    fn is_picking_bookmark(&self) -> bool {
+        !CONFIG.torrents_tab.directory_bookmarks.is_empty()
+            && (self.picking_bookmark || self.text().is_empty())
+    }
+
+    /// Starts listing the directory the current text is in, if it's reachable
+    /// and not listed already.
+    fn list_parent(&mut self) {
+        let text = self.text();
+        let Some((parent, _)) = text.rsplit_once('/') else {
+            return;
+        };
+        if self.listed_parent.as_deref() == Some(parent) {
+            return;
+        }
+        self.listed_parent = Some(parent.to_string());
+
+        let Some(dir) = local_dir(&format!("{parent}/")) else {
+            return;
+        };
+        let parent = parent.to_string();
+        let listing = Arc::clone(&self.listing);
+        tokio::task::spawn_blocking(move || {
+            let subdirs = fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect();
+            *listing.lock().unwrap() = Listing { parent, subdirs };
+        });
+    }
+
+    fn is_listed_dir(&self, text: &str) -> bool {
+        let Some((parent, name)) = text.rsplit_once('/') else {
+            return false;
+        };
+        let listing = self.listing.lock().unwrap();
+        listing.parent == parent && listing.subdirs.iter().any(|subdir| subdir == name)
+    }
+
+    fn candidates(&self, text: &str) -> Vec<String> {
+        let mut candidates: BTreeSet<String> = self
+            .known_dirs
+            .iter()
+            .filter(|dir| dir.starts_with(text) && *dir != text)
+            .cloned()
+            .collect();
+
+        let Some((parent, partial)) = text.rsplit_once('/') else {
+            return candidates.into_iter().collect();
+        };
+
+        // Only what's been listed by now, the listing isn't waited for.
+        let listing = self.listing.lock().unwrap();
+        if listing.parent == parent {
+            for name in &listing.subdirs {
+                let is_hidden = name.starts_with('.') && !partial.starts_with('.');
+
+                if name.starts_with(partial) && !is_hidden {
+                    candidates.insert(format!("{parent}/{name}"));
+                }
+            }
+        }
+
+        candidates.into_iter().collect()
+    }
+
+    fn hint_lines(&self) -> Vec<Line<'_>> {
+        if self.input_mgr.is_completing() || !self.is_picking_bookmark() {
+            return vec![];
+        }
+
+        let mut bookmarks = Line::default();
+        for (idx, bookmark) in CONFIG
+            .torrents_tab
+            .directory_bookmarks
+            .iter()
+            .take(9)
+            .enumerate()
+        {
+            if idx > 0 {
+                bookmarks.push_span(Span::raw(" | "));
+            }
+            bookmarks.push_span(Span::styled((idx + 1).to_string(), keybinding_style()));
+            bookmarks.push_span(Span::raw(format!(" {bookmark}")));
+        }
+
+        if bookmarks.spans.is_empty() {
+            vec![]
+        } else {
+            vec![bookmarks]
+        }
+    }
+}
+
+impl Component for DirectoryInput {
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let lines = self.hint_lines();
+        // Shown right above the prompt, over whatever is there.
+        let height = u16::try_from(lines.len()).unwrap_or(u16::MAX).min(rect.y);
+        if height > 0 {
+            let hints_rect = Rect {
+                y: rect.y - height,
+                height,
+                ..rect
+            };
+            f.render_widget(Clear, hints_rect);
+            f.render_widget(Paragraph::new(lines), hints_rect);
+        }
+
+        self.input_mgr.render(f, rect);
+    }
+}
+
+/// Where a directory of the daemon is reachable locally, if it is.
+fn local_dir(remote_dir: &str) -> Option<PathBuf> {
+    match CONFIG.connection.mapped_local_path(remote_dir) {
+        Some(local) => Some(PathBuf::from(local)),
+        None if CONFIG.connection.is_local() => Some(PathBuf::from(remote_dir)),
+        None => None,
+    }
+}
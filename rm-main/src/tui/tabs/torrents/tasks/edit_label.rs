@@ -0,0 +1,98 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use rm_shared::{
+    action::{Action, UpdateAction},
+    status_task::StatusTask,
+};
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        components::{Component, ComponentAction, InputManager},
+        ctx::CTX,
+    },
+};
+
+use super::TorrentSelection;
+
+#[derive(Clone, Copy)]
+pub enum LabelEdit {
+    Add,
+    Remove,
+}
+
+pub struct EditLabel {
+    selection: TorrentSelection,
+    edit: LabelEdit,
+    input_mgr: InputManager,
+}
+
+impl EditLabel {
+    pub fn new(selection: TorrentSelection, edit: LabelEdit, autocompletions: Vec<String>) -> Self {
+        let prompt = match edit {
+            LabelEdit::Add => "Add label: ",
+            LabelEdit::Remove => "Remove label: ",
+        };
+
+        Self {
+            selection,
+            edit,
+            input_mgr: InputManager::new(prompt.to_string()).autocompletions(autocompletions),
+        }
+    }
+
+    fn apply(&self) {
+        let label = self.input_mgr.text();
+
+        if label.is_empty() {
+            return;
+        }
+
+        let (task, action) = match self.edit {
+            LabelEdit::Add => (
+                StatusTask::new_add_label(label.clone()),
+                TorrentAction::AddLabel(self.selection.ids(), label),
+            ),
+            LabelEdit::Remove => (
+                StatusTask::new_remove_label(label.clone()),
+                TorrentAction::RemoveLabel(self.selection.ids(), label),
+            ),
+        };
+
+        CTX.send_update_action(UpdateAction::StatusTaskSet(task));
+        CTX.send_torrent_action(action);
+    }
+}
+
+impl Component for EditLabel {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Input(input) => {
+                if input.code == KeyCode::Esc {
+                    return ComponentAction::Quit;
+                } else if input.code == KeyCode::Enter {
+                    self.apply();
+                    return ComponentAction::Quit;
+                }
+
+                if self.input_mgr.handle_key(input).is_some() {
+                    CTX.send_action(Action::Render);
+                }
+
+                ComponentAction::Nothing
+            }
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text).is_some() {
+                    CTX.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
+
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        self.input_mgr.render(f, rect)
+    }
+}
@@ -1,10 +1,11 @@
 use crossterm::event::KeyCode;
 use ratatui::prelude::*;
 
+use rm_config::keymap::InputAction;
 use rm_shared::action::{Action, UpdateAction};
 
 use crate::tui::{
-    components::{Component, ComponentAction, InputManager},
+    components::{input_action, Component, ComponentAction, InputManager},
     ctx::CTX,
 };
 
@@ -15,7 +16,7 @@ pub struct Filter {
 impl Filter {
     pub fn new(current_pattern: &Option<String>) -> Self {
         let pattern = current_pattern.as_ref().cloned().unwrap_or_default();
-        let input = InputManager::new_with_value("Search: ".to_string(), pattern);
+        let input = InputManager::new_with_value("Search: ".to_string(), pattern).history("filter");
         Self { input }
     }
 }
@@ -24,7 +25,14 @@ impl Component for Filter {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         match action {
             Action::Input(input) => {
-                if matches!(input.code, KeyCode::Enter | KeyCode::Esc) {
+                if input_action(&input) == Some(InputAction::SelectAllMatching) {
+                    self.input.save_history();
+                    CTX.send_update_action(UpdateAction::SearchFilterSelectAll);
+                    ComponentAction::Quit
+                } else if matches!(input.code, KeyCode::Enter | KeyCode::Esc) {
+                    if input.code == KeyCode::Enter {
+                        self.input.save_history();
+                    }
                     if self.input.text().is_empty() {
                         CTX.send_update_action(UpdateAction::SearchFilterClear);
                     }
@@ -36,6 +44,12 @@ impl Component for Filter {
                     ComponentAction::Nothing
                 }
             }
+            Action::Paste(text) => {
+                if self.input.paste(&text).is_some() {
+                    CTX.send_update_action(UpdateAction::SearchFilterApply(self.input.text()));
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
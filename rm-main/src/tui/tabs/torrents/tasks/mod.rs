@@ -2,6 +2,8 @@ mod add_magnet;
 mod change_category;
 mod default;
 mod delete_torrent;
+mod directory_input;
+mod edit_label;
 mod filter;
 mod move_torrent;
 mod rename;
@@ -13,6 +15,7 @@ pub use add_magnet::AddMagnet;
 pub use change_category::ChangeCategory;
 pub use default::Default;
 pub use delete_torrent::Delete;
+pub use edit_label::{EditLabel, LabelEdit};
 pub use filter::Filter;
 pub use move_torrent::Move;
 pub use rename::Rename;
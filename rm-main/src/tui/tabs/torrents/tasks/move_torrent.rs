@@ -8,30 +8,37 @@ use rm_shared::{
 use crate::{
     transmission::TorrentAction,
     tui::{
-        components::{Component, ComponentAction, InputManager},
+        components::{Component, ComponentAction},
         ctx::CTX,
     },
 };
 
-use super::TorrentSelection;
+use super::{directory_input::DirectoryInput, TorrentSelection};
 
 pub struct Move {
     selection: TorrentSelection,
-    input_mgr: InputManager,
+    input_mgr: DirectoryInput,
 }
 
 impl Move {
-    pub fn new(selection: TorrentSelection, existing_location: String) -> Self {
+    pub fn new(
+        selection: TorrentSelection,
+        existing_location: String,
+        known_dirs: Vec<String>,
+    ) -> Self {
         let prompt = "New directory: ".to_string();
 
         Self {
             selection,
-            input_mgr: InputManager::new_with_value(prompt, existing_location),
+            input_mgr: DirectoryInput::new(prompt, existing_location)
+                .known_dirs(known_dirs)
+                .history("directory"),
         }
     }
 
     fn handle_input(&mut self, input: KeyEvent) -> ComponentAction {
         if input.code == KeyCode::Enter {
+            self.input_mgr.save_history();
             let new_location = self.input_mgr.text();
 
             let torrent_action = TorrentAction::Move(self.selection.ids(), new_location.clone());
@@ -56,6 +63,12 @@ impl Component for Move {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         match action {
             Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text).is_some() {
+                    CTX.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
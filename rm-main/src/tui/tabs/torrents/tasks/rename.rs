@@ -26,7 +26,7 @@ impl Rename {
 
         Self {
             id: to_rename,
-            input_mgr: InputManager::new_with_value(prompt, curr_name.clone()),
+            input_mgr: InputManager::new_with_value(prompt, curr_name.clone()).history("rename"),
             curr_name,
         }
     }
@@ -56,6 +56,7 @@ impl Component for Rename {
                 if input.code == KeyCode::Esc {
                     return ComponentAction::Quit;
                 } else if input.code == KeyCode::Enter {
+                    self.input_mgr.save_history();
                     self.rename();
                     return ComponentAction::Quit;
                 }
@@ -66,6 +67,12 @@ impl Component for Rename {
 
                 ComponentAction::Nothing
             }
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text).is_some() {
+                    CTX.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
 
             _ => ComponentAction::Nothing,
         }
@@ -1,38 +1,64 @@
 use crate::tui::components::{keybinding_style, Component};
-use rm_config::{keymap::GeneralAction, CONFIG};
+use rm_config::{
+    keymap::{GeneralAction, TorrentsAction},
+    CONFIG,
+};
+use rm_shared::utils::bytes_to_human_format;
 
 use ratatui::{prelude::*, text::Span};
 
 pub struct Selection {
     selection_amount: usize,
+    selection_size: i64,
+    is_visual: bool,
 }
 
 impl Selection {
-    pub const fn new(selection_amount: usize) -> Self {
-        Self { selection_amount }
+    pub const fn new(selection_amount: usize, selection_size: i64, is_visual: bool) -> Self {
+        Self {
+            selection_amount,
+            selection_size,
+            is_visual,
+        }
     }
 }
 
 impl Component for Selection {
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         let mut line = Line::default();
-        let mut line_is_empty = true;
+
+        if self.is_visual {
+            line.push_span(Span::styled("VISUAL", keybinding_style()));
+            line.push_span(Span::raw(" | "));
+
+            if let Some(keys) = CONFIG
+                .keybindings
+                .torrents_tab
+                .get_keys_for_action_joined(TorrentsAction::VisualSelect)
+            {
+                line.push_span(Span::styled(keys, keybinding_style()));
+                line.push_span(Span::raw(" - end range | "));
+            }
+        }
 
         if let Some(keys) = CONFIG
             .keybindings
             .general
             .get_keys_for_action_joined(GeneralAction::Close)
         {
-            line_is_empty = false;
             line.push_span(Span::styled(keys, keybinding_style()));
-            line.push_span(Span::raw(" - clear selection"));
-        }
-
-        if !line_is_empty {
-            line.push_span(Span::raw(" | "));
+            if self.is_visual {
+                line.push_span(Span::raw(" - cancel range | "));
+            } else {
+                line.push_span(Span::raw(" - clear selection | "));
+            }
         }
 
-        line.push_span(format!("{} selected", self.selection_amount));
+        line.push_span(format!(
+            "{} selected ({})",
+            self.selection_amount,
+            bytes_to_human_format(self.selection_size)
+        ));
 
         f.render_widget(line, rect);
     }
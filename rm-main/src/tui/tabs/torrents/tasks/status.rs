@@ -1,4 +1,5 @@
 use ratatui::{prelude::*, style::Style};
+use rm_config::CONFIG;
 use rm_shared::{
     action::{Action, UpdateAction},
     status_task::StatusTask,
@@ -41,19 +42,19 @@ impl Component for Status {
                 let status_text = self.task.loading_str();
                 let default_throbber = throbber_widgets_tui::Throbber::default()
                     .label(status_text)
-                    .style(Style::default().yellow());
+                    .style(Style::default().fg(CONFIG.theme.warning));
                 f.render_stateful_widget(default_throbber.clone(), rect, state);
             }
             CurrentTaskState::Failure(_) => {
                 let line = Line::from(vec![
-                    Span::styled(" ", Style::default().red()),
+                    Span::styled(" ", Style::default().fg(CONFIG.theme.error)),
                     Span::raw(self.task.failure_str()),
                 ]);
                 f.render_widget(line, rect);
             }
             CurrentTaskState::Success(_) => {
                 let line = Line::from(vec![
-                    Span::styled(" ", Style::default().green()),
+                    Span::styled(" ", Style::default().fg(CONFIG.theme.success)),
                     Span::raw(self.task.success_str()),
                 ]);
                 f.render_widget(line, rect);
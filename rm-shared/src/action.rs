@@ -4,7 +4,7 @@ use crossterm::event::KeyEvent;
 use magnetease::{MagneteaseError, MagneteaseResult};
 use transmission_rpc::types::{FreeSpace, SessionGet, SessionStats, Torrent};
 
-use crate::{current_window::TorrentWindow, status_task::StatusTask};
+use crate::{current_window::TorrentWindow, metainfo::PendingTorrent, status_task::StatusTask};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -31,18 +31,41 @@ pub enum Action {
     ChangeTab(u8),
     XdgOpen,
     Input(KeyEvent),
+    /// Text pasted while in input mode.
+    Paste(String),
+    /// Magnet or URL pasted while in normal mode.
+    PasteLink(String),
+    /// Left click at this column and row.
+    Click(u16, u16),
+    /// Second left click at the same column and row shortly after the first.
+    DoubleClick(u16, u16),
     MoveToColumnLeft,
     MoveToColumnRight,
     // Torrents Tab
     ShowStats,
     ShowFiles,
     Pause,
+    PauseAll,
+    ResumeAll,
     Delete,
     AddMagnet,
+    AddTorrentFile,
     MoveTorrent,
     ChangeCategory,
+    AddLabel,
+    RemoveLabel,
+    VisualSelect,
+    SelectAll,
+    InvertSelection,
+    EditColumns,
+    CycleGrouping,
+    ToggleGroups,
     ChangeFilePriority,
     Rename,
+    BatchRename,
+    SelectByGlob,
+    /// Switch to the torrents tab and highlight the torrent with this ID.
+    JumpToTorrent(i64),
     // Search Tab
     ShowProvidersInfo,
 }
@@ -52,6 +75,7 @@ pub enum UpdateAction {
     SwitchToInputMode,
     SwitchToNormalMode,
     Error(Box<ErrorMessage>),
+    DuplicateTorrent(Box<DuplicateTorrent>),
     UnrecoverableError(Box<color_eyre::eyre::Report>),
     // Torrents Tab
     ChangeTorrentWindow(TorrentWindow),
@@ -62,7 +86,9 @@ pub enum UpdateAction {
     UpdateCurrentTorrent(Box<Torrent>),
     SearchFilterApply(String),
     SearchFilterClear,
+    SearchFilterSelectAll,
     CancelTorrentTask,
+    PreviewTorrent(Box<PendingTorrent>),
     // Search Tab
     SearchStarted,
     ProviderResult(MagneteaseResult),
@@ -76,6 +102,16 @@ pub enum UpdateAction {
     StatusTaskSetSuccess(StatusTask),
 }
 
+/// A torrent that was about to be added while the daemon already has it.
+pub struct DuplicateTorrent {
+    pub id: i64,
+    pub name: String,
+    /// The daemon's tracker list of the torrent, tiers separated with empty lines.
+    pub tracker_list: String,
+    /// Trackers of the added magnet or .torrent file that the torrent lacks.
+    pub new_trackers: Vec<String>,
+}
+
 #[derive(Debug)]
 pub struct ErrorMessage {
     pub title: String,
@@ -0,0 +1,258 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Maximum nesting of lists and dictionaries, so that malicious input can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// A decoded bencode value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Value>),
+    Dict(BTreeMap<Vec<u8>, Value>),
+}
+
+impl Value {
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// The value as a string, with invalid UTF-8 replaced.
+    pub fn as_str_lossy(&self) -> Option<String> {
+        self.as_bytes()
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Self::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_dict(&self) -> Option<&BTreeMap<Vec<u8>, Value>> {
+        match self {
+            Self::Dict(dict) => Some(dict),
+            _ => None,
+        }
+    }
+
+    /// Looks up `key` if the value is a dictionary.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_dict()?.get(key.as_bytes())
+    }
+}
+
+#[derive(Debug)]
+pub struct BencodeError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for BencodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for BencodeError {}
+
+/// Decodes a single bencoded value spanning the whole input.
+pub fn decode(input: &[u8]) -> Result<Value, BencodeError> {
+    let mut parser = Parser { input, pos: 0 };
+    let value = parser.value(0)?;
+
+    if parser.pos != input.len() {
+        return Err(parser.error("trailing data after the value"));
+    }
+
+    Ok(value)
+}
+
+/// Finds `key` in the top-level dictionary and returns its value still encoded,
+/// byte for byte as it appears in the input.
+pub fn raw_dict_value<'a>(input: &'a [u8], key: &str) -> Result<Option<&'a [u8]>, BencodeError> {
+    let mut parser = Parser { input, pos: 0 };
+
+    if parser.peek() != Some(b'd') {
+        return Err(parser.error("expected a dictionary"));
+    }
+    parser.pos += 1;
+
+    while parser.peek() != Some(b'e') {
+        let current_key = parser.bytes()?;
+        let start = parser.pos;
+        parser.value(1)?;
+        if current_key == key.as_bytes() {
+            return Ok(Some(&input[start..parser.pos]));
+        }
+    }
+
+    Ok(None)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: impl Into<String>) -> BencodeError {
+        BencodeError {
+            position: self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, BencodeError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("too deeply nested"));
+        }
+
+        match self.peek() {
+            Some(b'i') => {
+                self.pos += 1;
+                let int = self.int_until(b'e')?;
+                Ok(Value::Int(int))
+            }
+            Some(b'l') => {
+                self.pos += 1;
+                let mut list = vec![];
+                while self.peek() != Some(b'e') {
+                    list.push(self.value(depth + 1)?);
+                }
+                self.pos += 1;
+                Ok(Value::List(list))
+            }
+            Some(b'd') => {
+                self.pos += 1;
+                let mut dict = BTreeMap::new();
+                while self.peek() != Some(b'e') {
+                    let key = self.bytes()?;
+                    let value = self.value(depth + 1)?;
+                    dict.insert(key, value);
+                }
+                self.pos += 1;
+                Ok(Value::Dict(dict))
+            }
+            Some(b'0'..=b'9') => Ok(Value::Bytes(self.bytes()?)),
+            Some(byte) => Err(self.error(format!("unexpected byte {byte:#04x}"))),
+            None => Err(self.error("unexpected end of data")),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>, BencodeError> {
+        if !matches!(self.peek(), Some(b'0'..=b'9')) {
+            return Err(self.error("expected a string"));
+        }
+
+        let len = usize::try_from(self.int_until(b':')?)
+            .map_err(|_| self.error("negative string length"))?;
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.input.len())
+            .ok_or_else(|| self.error("string runs past the end of data"))?;
+
+        let bytes = self.input[self.pos..end].to_vec();
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn int_until(&mut self, terminator: u8) -> Result<i64, BencodeError> {
+        let start = self.pos;
+        let len = self.input[start..]
+            .iter()
+            .position(|byte| *byte == terminator)
+            .ok_or_else(|| self.error("unterminated number"))?;
+
+        let digits = std::str::from_utf8(&self.input[start..start + len])
+            .map_err(|_| self.error("invalid number"))?;
+        let int = digits
+            .parse()
+            .map_err(|_| self.error(format!("invalid number \"{digits}\"")))?;
+
+        self.pos = start + len + 1;
+        Ok(int)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(string: &str) -> Value {
+        Value::Bytes(string.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn decodes_values() {
+        assert_eq!(decode(b"i42e").unwrap(), Value::Int(42));
+        assert_eq!(decode(b"i-7e").unwrap(), Value::Int(-7));
+        assert_eq!(decode(b"4:spam").unwrap(), bytes("spam"));
+        assert_eq!(decode(b"0:").unwrap(), bytes(""));
+        assert_eq!(
+            decode(b"l4:spami1ee").unwrap(),
+            Value::List(vec![bytes("spam"), Value::Int(1)])
+        );
+
+        let dict = decode(b"d3:cow3:moo4:spaml1:aee").unwrap();
+        assert_eq!(dict.get("cow"), Some(&bytes("moo")));
+        assert_eq!(dict.get("spam"), Some(&Value::List(vec![bytes("a")])));
+        assert_eq!(dict.get("missing"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let error_at = |input: &[u8]| decode(input).unwrap_err().position;
+
+        assert_eq!(error_at(b""), 0);
+        assert_eq!(error_at(b"x"), 0);
+        assert_eq!(error_at(b"i42"), 1);
+        assert_eq!(error_at(b"iabce"), 1);
+        assert_eq!(error_at(b"5:spam"), 2);
+        assert_eq!(error_at(b"-1:a"), 0);
+        assert_eq!(error_at(b"l4:spam"), 7);
+        assert_eq!(error_at(b"di1e4:spame"), 1);
+        assert_eq!(error_at(b"i1ei2e"), 3);
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let nested = format!("{}{}", "l".repeat(MAX_DEPTH + 2), "e".repeat(MAX_DEPTH + 2));
+        assert!(decode(nested.as_bytes()).is_err());
+
+        let fine = format!("{}{}", "l".repeat(MAX_DEPTH), "e".repeat(MAX_DEPTH));
+        assert!(decode(fine.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn finds_raw_values() {
+        let input = b"d8:announce3:url4:infod4:name1:xee";
+
+        assert_eq!(
+            raw_dict_value(input, "info").unwrap(),
+            Some(&b"d4:name1:xe"[..])
+        );
+        assert_eq!(
+            raw_dict_value(input, "announce").unwrap(),
+            Some(&b"3:url"[..])
+        );
+        assert_eq!(raw_dict_value(input, "comment").unwrap(), None);
+        assert!(raw_dict_value(b"l4:infoe", "info").is_err());
+    }
+}
@@ -8,6 +8,10 @@ pub enum Window {
 pub enum TorrentWindow {
     General,
     FileViewer,
+    Inspector,
+    Statistics,
+    TorrentPicker,
+    ColumnEditor,
 }
 
 #[derive(Debug, Clone, Copy)]
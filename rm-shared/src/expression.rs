@@ -0,0 +1,324 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Numeric torrent fields that can be referenced from user expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TorrentField {
+    Id,
+    SizeWhenDone,
+    TotalSize,
+    Progress,
+    Eta,
+    DownloadRate,
+    UploadRate,
+    UploadRatio,
+    UploadedEver,
+    DownloadedEver,
+    PeersConnected,
+    SecondsSeeding,
+    Availability,
+    QueuePosition,
+    AddedDate,
+    ActivityDate,
+    DoneDate,
+    Now,
+}
+
+impl TorrentField {
+    const ALL: [(&'static str, Self); 18] = [
+        ("id", Self::Id),
+        ("size_when_done", Self::SizeWhenDone),
+        ("total_size", Self::TotalSize),
+        ("progress", Self::Progress),
+        ("eta", Self::Eta),
+        ("download_rate", Self::DownloadRate),
+        ("upload_rate", Self::UploadRate),
+        ("upload_ratio", Self::UploadRatio),
+        ("uploaded_ever", Self::UploadedEver),
+        ("downloaded_ever", Self::DownloadedEver),
+        ("peers_connected", Self::PeersConnected),
+        ("seconds_seeding", Self::SecondsSeeding),
+        ("availability", Self::Availability),
+        ("queue_position", Self::QueuePosition),
+        ("added_date", Self::AddedDate),
+        ("activity_date", Self::ActivityDate),
+        ("done_date", Self::DoneDate),
+        ("now", Self::Now),
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(field_name, _)| *field_name == name)
+            .map(|(_, field)| *field)
+    }
+
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        Self::ALL.iter().map(|(name, _)| *name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    Field(TorrentField),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+impl Expression {
+    /// Evaluates the expression, asking `field_value` for every referenced field.
+    pub fn eval(&self, field_value: &impl Fn(TorrentField) -> f64) -> f64 {
+        match self {
+            Expression::Number(number) => *number,
+            Expression::Field(field) => field_value(*field),
+            Expression::Negate(expr) => -expr.eval(field_value),
+            Expression::Binary(lhs, op, rhs) => {
+                let lhs = lhs.eval(field_value);
+                let rhs = rhs.eval(field_value);
+                match op {
+                    Operator::Add => lhs + rhs,
+                    Operator::Sub => lhs - rhs,
+                    Operator::Mul => lhs * rhs,
+                    Operator::Div => lhs / rhs,
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.char_indices().collect(),
+            pos: 0,
+        };
+
+        let expr = parser.expression()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected character '{c}'")));
+        }
+
+        Ok(expr)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let expr = String::deserialize(deserializer)?;
+        expr.parse()
+            .map_err(|e| de::Error::custom(format!("invalid expression \"{expr}\": {e}")))
+    }
+}
+
+// expression := term (('+' | '-') term)*
+// term       := factor (('*' | '/') factor)*
+// factor     := '-' factor | number | field | '(' expression ')'
+struct Parser {
+    chars: Vec<(usize, char)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|(_, c)| *c)
+    }
+
+    fn byte_pos(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map(|(idx, _)| *idx)
+            .unwrap_or_else(|| self.chars.last().map_or(0, |(idx, c)| idx + c.len_utf8()))
+    }
+
+    fn error(&self, message: impl Into<String>) -> ExpressionError {
+        ExpressionError {
+            position: self.byte_pos(),
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(c) = self.peek().filter(|c| predicate(*c)) {
+            taken.push(c);
+            self.pos += 1;
+        }
+        taken
+    }
+
+    fn expression(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.term()?;
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Sub,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            let rhs = self.term()?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.factor()?;
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek() {
+                Some('*') => Operator::Mul,
+                Some('/') => Operator::Div,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            let rhs = self.factor()?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expression, ExpressionError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(Expression::Negate(Box::new(self.factor()?)))
+            }
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.expression()?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected ')'"));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.byte_pos();
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                number
+                    .parse()
+                    .map(Expression::Number)
+                    .map_err(|_| ExpressionError {
+                        position: start,
+                        message: format!("invalid number \"{number}\""),
+                    })
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let start = self.byte_pos();
+                let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                TorrentField::from_name(&name)
+                    .map(Expression::Field)
+                    .ok_or_else(|| ExpressionError {
+                        position: start,
+                        message: format!(
+                            "unknown field \"{name}\", available fields: {}",
+                            TorrentField::names().collect::<Vec<_>>().join(", ")
+                        ),
+                    })
+            }
+            Some(c) => Err(self.error(format!("unexpected character '{c}'"))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expr: &str) -> f64 {
+        let expr: Expression = expr.parse().unwrap();
+        expr.eval(&|field| match field {
+            TorrentField::SizeWhenDone => 1000.0,
+            TorrentField::Progress => 0.25,
+            TorrentField::UploadRatio => 2.0,
+            _ => 0.0,
+        })
+    }
+
+    fn parse_error(expr: &str) -> ExpressionError {
+        expr.parse::<Expression>().unwrap_err()
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("2 * 3 + 1"), 7.0);
+        assert_eq!(eval("1 - 6 / 2"), -2.0);
+    }
+
+    #[test]
+    fn operators_of_same_precedence_are_left_associative() {
+        assert_eq!(eval("10 - 4 - 3"), 3.0);
+        assert_eq!(eval("24 / 4 / 2"), 3.0);
+    }
+
+    #[test]
+    fn parentheses_and_negation() {
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("-(1 + 2)"), -3.0);
+        assert_eq!(eval("--2"), 2.0);
+        assert_eq!(eval("2 * -3"), -6.0);
+    }
+
+    #[test]
+    fn fields_are_evaluated() {
+        assert_eq!(eval("size_when_done * (1 - progress)"), 750.0);
+        assert!(eval("upload_ratio") > eval("progress"));
+        assert_eq!(eval(" .5 * upload_ratio "), 1.0);
+    }
+
+    #[test]
+    fn unknown_field_is_reported_with_its_position() {
+        let err = parse_error("1 + sizes");
+        assert_eq!(err.position, 4);
+        assert!(err.message.starts_with("unknown field \"sizes\""));
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        assert_eq!(parse_error("").message, "unexpected end of expression");
+        assert_eq!(parse_error("1 +").position, 3);
+        assert_eq!(parse_error("(1 + 2").message, "expected ')'");
+        assert_eq!(parse_error("1 2").message, "unexpected character '2'");
+        assert_eq!(parse_error("1.2.3").message, "invalid number \"1.2.3\"");
+        assert_eq!(parse_error("1 % 2").position, 2);
+    }
+}
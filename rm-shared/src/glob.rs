@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+/// Case-insensitive shell-like pattern for matching torrent's file paths.
+///
+/// `*` and `?` don't match `/`, `**` matches any number of directories and
+/// `[abc]` matches any of the listed characters. Patterns without a `/` are
+/// matched against the file name only, so `*.nfo` matches in any directory.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    regex: Regex,
+    match_name_only: bool,
+}
+
+impl Glob {
+    pub fn is_match(&self, path: &str) -> bool {
+        if self.match_name_only {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            self.regex.is_match(name)
+        } else {
+            self.regex.is_match(path)
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl FromStr for Glob {
+    type Err = regex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut regex = String::from("(?i)^");
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                '[' => {
+                    regex.push('[');
+                    if chars.peek() == Some(&'!') {
+                        chars.next();
+                        regex.push('^');
+                    }
+                    for c in chars.by_ref() {
+                        if c == ']' {
+                            break;
+                        }
+                        if c == '\\' || c == '[' {
+                            regex.push('\\');
+                        }
+                        regex.push(c);
+                    }
+                    regex.push(']');
+                }
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+
+        Ok(Self {
+            pattern: s.to_string(),
+            regex: Regex::new(&regex)?,
+            match_name_only: !s.contains('/'),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Glob {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let glob = String::deserialize(deserializer)?;
+        glob.parse()
+            .map_err(|e| de::Error::custom(format!("invalid pattern \"{glob}\": {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str) -> Glob {
+        pattern.parse().unwrap()
+    }
+
+    #[test]
+    fn patterns_without_slash_match_file_names() {
+        let nfo = glob("*.nfo");
+
+        assert!(nfo.is_match("release.nfo"));
+        assert!(nfo.is_match("Release/Info/RELEASE.NFO"));
+        assert!(!nfo.is_match("release.nfo.txt"));
+    }
+
+    #[test]
+    fn single_wildcards_stop_at_slashes() {
+        let sample = glob("Release/*.mkv");
+
+        assert!(sample.is_match("Release/movie.mkv"));
+        assert!(!sample.is_match("Release/Sample/sample.mkv"));
+        assert!(glob("Release/?.txt").is_match("Release/a.txt"));
+        assert!(!glob("Release/?.txt").is_match("Release/ab.txt"));
+    }
+
+    #[test]
+    fn double_wildcards_cross_directories() {
+        let samples = glob("**/Sample/*");
+
+        assert!(samples.is_match("Sample/sample.mkv"));
+        assert!(samples.is_match("Release/Sample/sample.mkv"));
+        assert!(!samples.is_match("Release/sample.mkv"));
+        assert!(glob("Release/**").is_match("Release/a/b/c.txt"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(glob("cd[12].iso").is_match("CD2.iso"));
+        assert!(!glob("cd[12].iso").is_match("cd3.iso"));
+        assert!(glob("cd[!12].iso").is_match("cd3.iso"));
+        assert!(!glob("cd[!12].iso").is_match("cd1.iso"));
+    }
+
+    #[test]
+    fn regex_characters_are_literal() {
+        assert!(glob("(1+1).txt").is_match("(1+1).txt"));
+        assert!(!glob("a.txt").is_match("abtxt"));
+    }
+}
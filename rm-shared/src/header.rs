@@ -20,9 +20,66 @@ pub enum Header {
     SmallStatus,
     Category,
     CategoryIcon,
+    Status,
+    Seeds,
+    Leeches,
+    TotalSize,
+    DownloadedEver,
+    DoneDate,
+    Tracker,
+    InfoHash,
+    QueuePosition,
+    Private,
+    Comment,
+    Creator,
+    SecondsSeeding,
+    Availability,
+    Labels,
+    DownloadHistory,
+    UploadHistory,
+    /// Index into the user-defined `custom_headers` list.
+    Custom(usize),
 }
 
 impl Header {
+    /// Every header but the custom ones.
+    pub const ALL: [Self; 34] = [
+        Self::Id,
+        Self::Name,
+        Self::SizeWhenDone,
+        Self::Progress,
+        Self::Eta,
+        Self::DownloadRate,
+        Self::UploadRate,
+        Self::DownloadDir,
+        Self::Padding,
+        Self::UploadRatio,
+        Self::UploadedEver,
+        Self::ActivityDate,
+        Self::AddedDate,
+        Self::PeersConnected,
+        Self::SmallStatus,
+        Self::Category,
+        Self::CategoryIcon,
+        Self::Status,
+        Self::Seeds,
+        Self::Leeches,
+        Self::TotalSize,
+        Self::DownloadedEver,
+        Self::DoneDate,
+        Self::Tracker,
+        Self::InfoHash,
+        Self::QueuePosition,
+        Self::Private,
+        Self::Comment,
+        Self::Creator,
+        Self::SecondsSeeding,
+        Self::Availability,
+        Self::Labels,
+        Self::DownloadHistory,
+        Self::UploadHistory,
+    ];
+
     pub fn default_constraint(&self) -> Constraint {
         match self {
             Self::Name => Constraint::Max(70),
@@ -42,6 +99,24 @@ impl Header {
             Self::SmallStatus => Constraint::Length(1),
             Self::Category => Constraint::Max(15),
             Self::CategoryIcon => Constraint::Length(5),
+            Self::Status => Constraint::Length(16),
+            Self::Seeds => Constraint::Length(10),
+            Self::Leeches => Constraint::Length(10),
+            Self::TotalSize => Constraint::Length(12),
+            Self::DownloadedEver => Constraint::Length(12),
+            Self::DoneDate => Constraint::Length(14),
+            Self::Tracker => Constraint::Max(30),
+            Self::InfoHash => Constraint::Length(40),
+            Self::QueuePosition => Constraint::Length(5),
+            Self::Private => Constraint::Length(7),
+            Self::Comment => Constraint::Max(50),
+            Self::Creator => Constraint::Max(25),
+            Self::SecondsSeeding => Constraint::Length(12),
+            Self::Availability => Constraint::Length(12),
+            Self::Labels => Constraint::Max(30),
+            Self::DownloadHistory => Constraint::Length(12),
+            Self::UploadHistory => Constraint::Length(12),
+            Self::Custom(_) => Constraint::Length(12),
         }
     }
 
@@ -64,6 +139,25 @@ impl Header {
             Self::SmallStatus => "",
             Self::Category => "Category",
             Self::CategoryIcon => "",
+            Self::Status => "Status",
+            Self::Seeds => "Seeds",
+            Self::Leeches => "Leeches",
+            Self::TotalSize => "Total Size",
+            Self::DownloadedEver => "Down Ever",
+            Self::DoneDate => "Done",
+            Self::Tracker => "Tracker",
+            Self::InfoHash => "Hash",
+            Self::QueuePosition => "Queue",
+            Self::Private => "Private",
+            Self::Comment => "Comment",
+            Self::Creator => "Creator",
+            Self::SecondsSeeding => "Seeding Time",
+            Self::Availability => "Availability",
+            Self::Labels => "Labels",
+            Self::DownloadHistory => "Down History",
+            Self::UploadHistory => "Up History",
+            // Resolved from the config, see `TorrentsTab::header_name`
+            Self::Custom(_) => "",
         }
     }
 }
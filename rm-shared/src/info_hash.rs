@@ -0,0 +1,33 @@
+use crate::bencode::{self, BencodeError};
+
+/// Info hash of a .torrent file, that is the SHA-1 of its encoded info dictionary.
+pub fn from_metainfo(contents: &[u8]) -> Result<String, BencodeError> {
+    let info = bencode::raw_dict_value(contents, "info")?.ok_or(BencodeError {
+        position: 0,
+        message: "missing info dictionary".to_string(),
+    })?;
+
+    Ok(sha1_smol::Sha1::from(info).digest().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_the_info_dictionary_as_encoded() {
+        let torrent = b"d8:announce9:udp://a/b4:infod6:lengthi1024e4:name8:file.isoee";
+
+        assert_eq!(
+            from_metainfo(torrent).unwrap(),
+            "f00ed586a3bbef9f2ce808e3ca6264997f0c8afa"
+        );
+    }
+
+    #[test]
+    fn torrents_without_info_are_rejected() {
+        assert!(from_metainfo(b"d8:announce9:udp://a/be").is_err());
+        assert!(from_metainfo(b"d4:infod4:name").is_err());
+        assert!(from_metainfo(b"i1e").is_err());
+    }
+}
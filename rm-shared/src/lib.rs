@@ -1,5 +1,11 @@
 pub mod action;
+pub mod bencode;
 pub mod current_window;
+pub mod expression;
+pub mod glob;
 pub mod header;
+pub mod info_hash;
+pub mod magnet;
+pub mod metainfo;
 pub mod status_task;
 pub mod utils;
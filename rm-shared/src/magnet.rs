@@ -0,0 +1,272 @@
+use std::fmt::Display;
+
+use url::Url;
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// Multihash prefix of a SHA-256 digest, the only one BitTorrent v2 uses.
+const SHA256_MULTIHASH_PREFIX: &str = "1220";
+const TRACKER_SCHEMES: [&str; 5] = ["udp", "http", "https", "ws", "wss"];
+
+/// Something that can be handed to the daemon to add a torrent from.
+pub enum Link {
+    Magnet(MagnetLink),
+    /// An http(s) URL of a .torrent file.
+    Url(Url),
+}
+
+impl Link {
+    /// Parses a magnet link or an http(s) URL, "www." ones included.
+    pub fn parse(text: &str) -> Result<Self, LinkError> {
+        let text = text.trim();
+
+        if text.starts_with("magnet:") {
+            return MagnetLink::parse(text).map(Self::Magnet);
+        }
+
+        let with_scheme = if text.starts_with("www.") {
+            format!("https://{text}")
+        } else {
+            text.to_string()
+        };
+        let url = Url::parse(&with_scheme).map_err(|_| {
+            if with_scheme.starts_with("http://") || with_scheme.starts_with("https://") {
+                LinkError::InvalidUrl
+            } else {
+                LinkError::NotALink
+            }
+        })?;
+
+        match url.scheme() {
+            "http" | "https" if url.host().is_some() => Ok(Self::Url(url)),
+            "http" | "https" => Err(LinkError::InvalidUrl),
+            _ => Err(LinkError::NotALink),
+        }
+    }
+}
+
+/// A magnet link with a v1 (btih) or v2 (btmh) info hash.
+pub struct MagnetLink {
+    /// v1 info hash as lowercase hex, the way the daemon reports it.
+    pub info_hash: Option<String>,
+    /// v2 info hash as lowercase hex, without the multihash prefix.
+    pub info_hash_v2: Option<String>,
+    pub display_name: Option<String>,
+    pub trackers: Vec<String>,
+    /// `tr` parameters that aren't usable tracker URLs, left out of `trackers`.
+    pub skipped_trackers: Vec<String>,
+}
+
+impl MagnetLink {
+    pub fn parse(uri: &str) -> Result<Self, LinkError> {
+        let url = Url::parse(uri).map_err(|_| LinkError::NotALink)?;
+        if url.scheme() != "magnet" {
+            return Err(LinkError::NotALink);
+        }
+
+        let mut magnet = Self {
+            info_hash: None,
+            info_hash_v2: None,
+            display_name: None,
+            trackers: vec![],
+            skipped_trackers: vec![],
+        };
+
+        for (key, value) in url.query_pairs() {
+            match &*key {
+                "xt" => {
+                    if let Some(hash) = value.strip_prefix("urn:btih:") {
+                        magnet.info_hash = Some(parse_btih(hash)?);
+                    } else if let Some(hash) = value.strip_prefix("urn:btmh:") {
+                        magnet.info_hash_v2 = Some(parse_btmh(hash)?);
+                    }
+                }
+                "dn" => magnet.display_name = Some(value.into_owned()),
+                "tr" => {
+                    let is_valid = Url::parse(&value).is_ok_and(|tracker| {
+                        TRACKER_SCHEMES.contains(&tracker.scheme()) && tracker.host().is_some()
+                    });
+                    if is_valid {
+                        magnet.trackers.push(value.into_owned());
+                    } else {
+                        magnet.skipped_trackers.push(value.into_owned());
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if magnet.info_hash.is_none() && magnet.info_hash_v2.is_none() {
+            return Err(LinkError::MissingHash);
+        }
+
+        Ok(magnet)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkError {
+    NotALink,
+    InvalidUrl,
+    MissingHash,
+    InvalidHash(String),
+}
+
+impl Display for LinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkError::NotALink => write!(f, "not a magnet link or an http(s) URL"),
+            LinkError::InvalidUrl => write!(f, "URL has no host"),
+            LinkError::MissingHash => write!(f, "magnet link has no btih or btmh hash"),
+            LinkError::InvalidHash(hash) => write!(f, "invalid info hash \"{hash}\""),
+        }
+    }
+}
+
+impl std::error::Error for LinkError {}
+
+/// Accepts the 40 character hex and the older 32 character base32 form.
+fn parse_btih(hash: &str) -> Result<String, LinkError> {
+    let invalid = || LinkError::InvalidHash(hash.to_string());
+
+    match hash.len() {
+        40 if hash.bytes().all(|byte| byte.is_ascii_hexdigit()) => Ok(hash.to_lowercase()),
+        32 => base32_to_hex(hash).ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_btmh(hash: &str) -> Result<String, LinkError> {
+    let digest = hash
+        .strip_prefix(SHA256_MULTIHASH_PREFIX)
+        .filter(|digest| digest.len() == 64 && digest.bytes().all(|byte| byte.is_ascii_hexdigit()))
+        .ok_or_else(|| LinkError::InvalidHash(hash.to_string()))?;
+
+    Ok(digest.to_lowercase())
+}
+
+fn base32_to_hex(encoded: &str) -> Option<String> {
+    let mut bits: u64 = 0;
+    let mut bits_no = 0;
+    let mut hex = String::with_capacity(40);
+
+    for byte in encoded.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|c| *c == byte.to_ascii_uppercase())?;
+        bits = (bits << 5) | value as u64;
+        bits_no += 5;
+
+        if bits_no >= 8 {
+            bits_no -= 8;
+            hex.push_str(&format!("{:02x}", (bits >> bits_no) & 0xff));
+        }
+    }
+
+    Some(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "c12fe1c06bba254a9dc9f519b335aa7c1367a88a";
+
+    #[test]
+    fn base32_hashes_are_converted_to_hex() {
+        assert_eq!(
+            base32_to_hex("YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKEK").as_deref(),
+            Some(HASH)
+        );
+        assert_eq!(
+            base32_to_hex("yex6dqdlxisuvhoj6um3gnnkpqjwpkek").as_deref(),
+            Some(HASH)
+        );
+        assert_eq!(base32_to_hex("YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKE1"), None);
+    }
+
+    #[test]
+    fn btih_hashes_in_both_forms() {
+        assert_eq!(parse_btih(&HASH.to_uppercase()).unwrap(), HASH);
+        assert_eq!(
+            parse_btih("YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKEK").unwrap(),
+            HASH
+        );
+        assert!(parse_btih(&HASH[1..]).is_err());
+        assert!(parse_btih(&HASH.replace('c', "x")).is_err());
+    }
+
+    #[test]
+    fn magnets_are_parsed() {
+        let magnet = MagnetLink::parse(&format!(
+            "magnet:?xt=urn:btih:{HASH}&dn=Some+Name&tr=udp%3A%2F%2Ftracker.example%3A1337"
+        ))
+        .unwrap();
+
+        assert_eq!(magnet.info_hash.as_deref(), Some(HASH));
+        assert_eq!(magnet.info_hash_v2, None);
+        assert_eq!(magnet.display_name.as_deref(), Some("Some Name"));
+        assert_eq!(magnet.trackers, ["udp://tracker.example:1337"]);
+    }
+
+    #[test]
+    fn v2_magnets_are_parsed() {
+        let digest = "ab".repeat(32);
+        let magnet = MagnetLink::parse(&format!("magnet:?xt=urn:btmh:1220{digest}")).unwrap();
+
+        assert_eq!(magnet.info_hash, None);
+        assert_eq!(magnet.info_hash_v2, Some(digest.clone()));
+        assert!(MagnetLink::parse(&format!("magnet:?xt=urn:btmh:1114{digest}")).is_err());
+    }
+
+    #[test]
+    fn unusable_trackers_are_skipped() {
+        let magnet = MagnetLink::parse(&format!(
+            "magnet:?xt=urn:btih:{HASH}&tr=wss://a.example/announce&tr=ws://b.example\
+             &tr=dht://c.example&tr=not-a-url"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            magnet.trackers,
+            ["wss://a.example/announce", "ws://b.example"]
+        );
+        assert_eq!(magnet.skipped_trackers, ["dht://c.example", "not-a-url"]);
+    }
+
+    #[test]
+    fn invalid_magnets_are_rejected() {
+        assert_eq!(
+            MagnetLink::parse("magnet:?dn=name").err(),
+            Some(LinkError::MissingHash)
+        );
+        assert_eq!(
+            MagnetLink::parse("magnet:?xt=urn:btih:abc").err(),
+            Some(LinkError::InvalidHash("abc".to_string()))
+        );
+        assert_eq!(
+            MagnetLink::parse("https://example.com").err(),
+            Some(LinkError::NotALink)
+        );
+    }
+
+    #[test]
+    fn links_are_told_apart() {
+        assert!(matches!(
+            Link::parse(&format!(" magnet:?xt=urn:btih:{HASH} ")),
+            Ok(Link::Magnet(_))
+        ));
+        assert!(matches!(
+            Link::parse("www.example.com/a.torrent"),
+            Ok(Link::Url(url)) if url.as_str() == "https://www.example.com/a.torrent"
+        ));
+        assert_eq!(Link::parse("http://").err(), Some(LinkError::InvalidUrl));
+        assert_eq!(
+            Link::parse("movies/a.torrent").err(),
+            Some(LinkError::NotALink)
+        );
+        assert_eq!(
+            Link::parse("C:\\a.torrent").err(),
+            Some(LinkError::NotALink)
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::bencode::{self, BencodeError, Value};
+
+/// Contents of a .torrent file that are shown before adding it.
+#[derive(Debug, Clone)]
+pub struct Metainfo {
+    pub name: String,
+    /// Files in the order the daemon indexes them, without BEP-47 padding files.
+    /// Empty for v2-only torrents, that list files in a "file tree" instead.
+    pub files: Vec<MetainfoFile>,
+    pub trackers: Vec<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MetainfoFile {
+    /// Path including the torrent's name, like the daemon reports it.
+    pub path: String,
+    pub length: i64,
+}
+
+impl Metainfo {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BencodeError> {
+        let root = bencode::decode(bytes)?;
+        let invalid = |message: &str| BencodeError {
+            position: 0,
+            message: message.to_string(),
+        };
+
+        let info = root
+            .get("info")
+            .ok_or_else(|| invalid("missing info dictionary"))?;
+        let name = utf8_field(info, "name").ok_or_else(|| invalid("missing name"))?;
+
+        let files = match (info.get("files"), info.get("length")) {
+            (Some(files), _) => {
+                let files = files
+                    .as_list()
+                    .ok_or_else(|| invalid("files is not a list"))?;
+                let mut metainfo_files = Vec::with_capacity(files.len());
+
+                for file in files {
+                    let is_padding = file
+                        .get("attr")
+                        .and_then(Value::as_bytes)
+                        .is_some_and(|attr| attr.contains(&b'p'));
+                    if is_padding {
+                        continue;
+                    }
+
+                    let length = file
+                        .get("length")
+                        .and_then(Value::as_int)
+                        .ok_or_else(|| invalid("file without length"))?;
+                    let path = file
+                        .get("path.utf-8")
+                        .or_else(|| file.get("path"))
+                        .and_then(Value::as_list)
+                        .ok_or_else(|| invalid("file without path"))?;
+
+                    let mut components = vec![name.clone()];
+                    components.extend(path.iter().filter_map(Value::as_str_lossy));
+                    metainfo_files.push(MetainfoFile {
+                        path: components.join("/"),
+                        length,
+                    });
+                }
+                metainfo_files
+            }
+            (None, Some(length)) => vec![MetainfoFile {
+                path: name.clone(),
+                length: length
+                    .as_int()
+                    .ok_or_else(|| invalid("length is not a number"))?,
+            }],
+            (None, None) if info.get("file tree").is_some() => vec![],
+            (None, None) => return Err(invalid("torrent lists no files")),
+        };
+
+        let mut trackers: Vec<String> = root
+            .get("announce-list")
+            .and_then(Value::as_list)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_list)
+            .flatten()
+            .filter_map(Value::as_str_lossy)
+            .collect();
+        if let Some(announce) = root.get("announce").and_then(Value::as_str_lossy) {
+            if !trackers.contains(&announce) {
+                trackers.insert(0, announce);
+            }
+        }
+
+        Ok(Self {
+            name,
+            files,
+            trackers,
+            comment: utf8_field(&root, "comment"),
+        })
+    }
+
+    pub fn total_size(&self) -> i64 {
+        self.files.iter().map(|file| file.length).sum()
+    }
+}
+
+/// A string field, preferring its `.utf-8` variant that some clients add.
+fn utf8_field(dict: &Value, key: &str) -> Option<String> {
+    dict.get(&format!("{key}.utf-8"))
+        .or_else(|| dict.get(key))
+        .and_then(Value::as_str_lossy)
+}
+
+/// A .torrent file waiting to be previewed before it's added.
+pub struct PendingTorrent {
+    pub metainfo: Metainfo,
+    /// Raw contents of the file, uploaded to the daemon as is.
+    pub contents: Vec<u8>,
+    pub download_dir: Option<String>,
+    pub category: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(torrent: &str) -> Result<Metainfo, BencodeError> {
+        Metainfo::from_bytes(torrent.as_bytes())
+    }
+
+    #[test]
+    fn single_file() {
+        let metainfo =
+            parse("d8:announce9:udp://a/b4:infod6:lengthi1024e4:name8:file.isoee").unwrap();
+
+        assert_eq!(metainfo.name, "file.iso");
+        assert_eq!(metainfo.files.len(), 1);
+        assert_eq!(metainfo.files[0].path, "file.iso");
+        assert_eq!(metainfo.total_size(), 1024);
+        assert_eq!(metainfo.trackers, ["udp://a/b"]);
+    }
+
+    #[test]
+    fn multi_file() {
+        let metainfo = parse(concat!(
+            "d13:announce-listll9:udp://a/bel9:udp://c/dee7:comment2:hi",
+            "4:infod5:filesld6:lengthi10e4:pathl1:a5:b.txteed6:lengthi5e4:pathl5:c.nfoee",
+            "e4:name7:Releaseee",
+        ))
+        .unwrap();
+
+        let paths: Vec<&str> = metainfo
+            .files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(paths, ["Release/a/b.txt", "Release/c.nfo"]);
+        assert_eq!(metainfo.total_size(), 15);
+        assert_eq!(metainfo.trackers, ["udp://a/b", "udp://c/d"]);
+        assert_eq!(metainfo.comment.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn padding_files_are_skipped() {
+        let metainfo = parse(concat!(
+            "d4:infod5:filesl",
+            "d6:lengthi10e4:pathl5:a.binee",
+            "d4:attr1:p6:lengthi6e4:pathl4:.pad1:6ee",
+            "d6:lengthi4e4:pathl5:b.binee",
+            "e4:name1:xee",
+        ))
+        .unwrap();
+
+        let paths: Vec<&str> = metainfo
+            .files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(paths, ["x/a.bin", "x/b.bin"]);
+        assert_eq!(metainfo.total_size(), 14);
+    }
+
+    #[test]
+    fn v2_only_torrents_have_no_files() {
+        let metainfo =
+            parse("d4:infod9:file treed5:a.bind0:d6:lengthi1eeee4:name1:x12:piece lengthi16384eee")
+                .unwrap();
+
+        assert!(metainfo.files.is_empty());
+    }
+
+    #[test]
+    fn malformed_torrents_are_rejected() {
+        assert!(parse("").is_err());
+        assert!(parse("d4:infod4:name1:xe").is_err());
+        assert!(parse("d8:announce3:urle").is_err());
+        assert!(parse("d4:infod6:lengthi1eee").is_err());
+        assert!(parse("d4:infod4:name1:xee").is_err());
+        assert!(parse("d4:infod5:filesld4:pathl1:aeee4:name1:xee").is_err());
+        assert!(parse("d4:infod6:length1:x4:name1:xee").is_err());
+    }
+}
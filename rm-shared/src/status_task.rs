@@ -13,6 +13,11 @@ enum TaskType {
     Move,
     Open,
     ChangeCategory,
+    AddLabel,
+    RemoveLabel,
+    Pause,
+    Resume,
+    Save,
 }
 
 impl StatusTask {
@@ -51,6 +56,41 @@ impl StatusTask {
         }
     }
 
+    pub fn new_add_label(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::AddLabel,
+            what: what.into(),
+        }
+    }
+
+    pub fn new_remove_label(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::RemoveLabel,
+            what: what.into(),
+        }
+    }
+
+    pub fn new_pause(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Pause,
+            what: what.into(),
+        }
+    }
+
+    pub fn new_resume(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Resume,
+            what: what.into(),
+        }
+    }
+
+    pub fn new_save(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Save,
+            what: what.into(),
+        }
+    }
+
     pub fn new_open(what: impl Into<String>) -> Self {
         StatusTask {
             task_type: TaskType::Open,
@@ -74,6 +114,11 @@ impl StatusTask {
                 }
             }
             TaskType::Rename => format!("Renamed {truncated}"),
+            TaskType::AddLabel => format!(" Added label {truncated}"),
+            TaskType::RemoveLabel => format!(" Removed label {truncated}"),
+            TaskType::Pause => format!(" Paused {truncated}"),
+            TaskType::Resume => format!(" Resumed {truncated}"),
+            TaskType::Save => format!(" Saved {truncated}"),
         }
     }
 
@@ -87,6 +132,11 @@ impl StatusTask {
             TaskType::Open => format!(" Error opening {truncated}"),
             TaskType::ChangeCategory => format!(" Error changing category to {truncated}"),
             TaskType::Rename => format!(" Error renaming {truncated}"),
+            TaskType::AddLabel => format!(" Error adding label {truncated}"),
+            TaskType::RemoveLabel => format!(" Error removing label {truncated}"),
+            TaskType::Pause => format!(" Error pausing {truncated}"),
+            TaskType::Resume => format!(" Error resuming {truncated}"),
+            TaskType::Save => format!(" Error saving {truncated}"),
         }
     }
 
@@ -100,6 +150,11 @@ impl StatusTask {
             TaskType::Open => format!(" Opening {truncated}"),
             TaskType::ChangeCategory => format!(" Changing category to {truncated}"),
             TaskType::Rename => format!(" Renaming {truncated}"),
+            TaskType::AddLabel => format!(" Adding label {truncated}"),
+            TaskType::RemoveLabel => format!(" Removing label {truncated}"),
+            TaskType::Pause => format!(" Pausing {truncated}"),
+            TaskType::Resume => format!(" Resuming {truncated}"),
+            TaskType::Save => format!(" Saving {truncated}"),
         }
     }
 }